@@ -1,12 +1,17 @@
-pub(crate) trait NonEmptyArray<const N_MINUS_ONE: usize> {
+pub trait NonEmptyArray<const N_MINUS_ONE: usize> {
     type Item;
     const N: usize;
 }
 
+/// Implemented for the same arrays as [`NonEmptyArray`], but without naming the length of the
+/// rest, so that an impl bounded by it can sit next to one for rank 0.
+pub trait NonEmpty {}
+
 pub(crate) trait NonEmptyArrayExt<const N_MINUS_ONE: usize>:
     NonEmptyArray<N_MINUS_ONE>
 {
     fn arr_split_first(&self) -> (&Self::Item, &[Self::Item; N_MINUS_ONE]);
+    #[allow(dead_code)]
     fn arr_split_first_mut(&mut self) -> (&mut Self::Item, &mut [Self::Item; N_MINUS_ONE]);
 }
 
@@ -33,6 +38,8 @@ macro_rules! impl_non_empty_array {
             type Item = T;
             const N: usize = $n;
         }
+
+        impl<T> NonEmpty for [T; $n] {}
     };
 }
 
@@ -52,6 +59,22 @@ impl_non_empty_array!(13);
 impl_non_empty_array!(14);
 impl_non_empty_array!(15);
 impl_non_empty_array!(16);
+impl_non_empty_array!(17);
+impl_non_empty_array!(18);
+impl_non_empty_array!(19);
+impl_non_empty_array!(20);
+impl_non_empty_array!(21);
+impl_non_empty_array!(22);
+impl_non_empty_array!(23);
+impl_non_empty_array!(24);
+impl_non_empty_array!(25);
+impl_non_empty_array!(26);
+impl_non_empty_array!(27);
+impl_non_empty_array!(28);
+impl_non_empty_array!(29);
+impl_non_empty_array!(30);
+impl_non_empty_array!(31);
+impl_non_empty_array!(32);
 
 #[cfg(test)]
 mod test {
@@ -95,4 +118,12 @@ mod test {
         remaining[1] = 456;
         assert_eq!(arr, [123, 2, 456, 4]);
     }
+
+    #[test]
+    fn test_arr_split_first_32() {
+        let arr: [usize; 32] = core::array::from_fn(|i| i);
+        let (first, remaining) = arr.arr_split_first();
+        assert_eq!(*first, 0);
+        assert_eq!(*remaining, core::array::from_fn::<_, 31, _>(|i| i + 1));
+    }
 }
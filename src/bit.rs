@@ -0,0 +1,179 @@
+use alloc::{format, vec, vec::Vec};
+use core::{fmt::Debug, ops::Index};
+
+use crate::{
+    checked_flat_of, len_of, offsets_of, shape_of, split_coords_of, DebugSlice, NonEmptyArray,
+};
+
+const BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitMultiVec<const N: usize> {
+    bits: Vec<u64>,
+    len: usize,
+    outer: usize,
+    sizes: [usize; N],
+    offsets: [usize; N],
+}
+
+impl<const N: usize> Debug for BitMultiVec<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let shape: Vec<usize> = [self.outer].into_iter().chain(self.sizes).collect();
+        let bits = FlatBits(self);
+        let slice = DebugSlice::new(f, &bits, &shape, &self.offsets);
+        f.debug_tuple(&format!("BitMultiVec<{N}>"))
+            .field(&slice)
+            .finish()
+    }
+}
+
+/// Indexes the bits of a grid by flat position, for formatting.
+struct FlatBits<'a, const N: usize>(&'a BitMultiVec<N>);
+
+impl<const N: usize> Index<usize> for FlatBits<'_, N> {
+    type Output = bool;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        if self.0.get_flat(i) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl<const N: usize> BitMultiVec<N> {
+    pub fn new(outer_size: usize, sizes: [usize; N]) -> Self {
+        let offsets = offsets_of(sizes).unwrap_or_else(|e| panic!("{e}"));
+        let len = len_of(outer_size, &offsets).unwrap_or_else(|e| panic!("{e}"));
+        Self {
+            bits: vec![0; len.div_ceil(BITS)],
+            len,
+            outer: outer_size,
+            sizes,
+            offsets,
+        }
+    }
+
+    pub fn from_fn(
+        outer_size: usize,
+        sizes: [usize; N],
+        mut f: impl FnMut(usize, [usize; N]) -> bool,
+    ) -> Self {
+        let mut grid = Self::new(outer_size, sizes);
+        for index in 0..grid.len {
+            let (i, coords) = split_coords_of(&grid.offsets, index);
+            grid.set_flat(index, f(i, coords));
+        }
+        grid
+    }
+
+    pub fn size(&self) -> usize {
+        self.outer
+    }
+
+    pub fn shape<const N_PLUS_ONE: usize>(&self) -> [usize; N_PLUS_ONE]
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        shape_of(self.outer, &self.sizes)
+    }
+
+    pub fn get<const N_PLUS_ONE: usize>(&self, coords: [usize; N_PLUS_ONE]) -> bool
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        self.get_flat(checked_flat_of(&self.offsets, self.shape(), &coords))
+    }
+
+    pub fn set<const N_PLUS_ONE: usize>(&mut self, coords: [usize; N_PLUS_ONE], value: bool)
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        self.set_flat(checked_flat_of(&self.offsets, self.shape(), &coords), value);
+    }
+
+    fn get_flat(&self, i: usize) -> bool {
+        self.bits[i / BITS] & (1 << (i % BITS)) != 0
+    }
+
+    fn set_flat(&mut self, i: usize, value: bool) {
+        let word = &mut self.bits[i / BITS];
+        if value {
+            *word |= 1 << (i % BITS);
+        } else {
+            *word &= !(1 << (i % BITS));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::BitMultiVec;
+    use crate::MultiVec;
+
+    #[test]
+    fn test_get_set() {
+        let mut m = BitMultiVec::<1>::new(3, [70]);
+        assert_eq!(m.size(), 3);
+        assert_eq!(m.shape(), [3, 70]);
+        assert_eq!(m.bits.len(), 4);
+        assert!(!m.get([2, 69]));
+        assert_eq!(BitMultiVec::<2>::new(3, [0, 5]).shape(), [3, 0, 5]);
+
+        m.set([1, 65], true);
+        m.set([2, 69], true);
+        assert!(m.get([1, 65]));
+        assert!(m.get([2, 69]));
+        assert!(!m.get([1, 64]));
+
+        m.set([1, 65], false);
+        assert!(!m.get([1, 65]));
+    }
+
+    #[test]
+    fn test_debug() {
+        let m = BitMultiVec::<1>::from_fn(2, [2], |i, [j]| i == j);
+        assert_eq!(
+            format!("{m:?}"),
+            "BitMultiVec<1>([[true, false], [false, true]])"
+        );
+
+        let m = BitMultiVec::<0>::from_fn(2000, [], |i, []| i % 1000 == 1);
+        assert_eq!(
+            format!("{m:?}"),
+            "BitMultiVec<0>([false, true, false, ..., false, false, false])"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range for dimension 1")]
+    fn test_get_out_of_range() {
+        BitMultiVec::<1>::new(3, [4]).get([0, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "shape is too large")]
+    fn test_new_overflow() {
+        BitMultiVec::<2>::new(1, [usize::MAX, 2]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_from_fn(outer_size in 0..10usize, sizes in prop::array::uniform2(0..10usize)) {
+            let f = |i: usize, [j, k]: [usize; 2]| (i * 7 + j * 3 + k).is_multiple_of(5);
+            let bits = BitMultiVec::<2>::from_fn(outer_size, sizes, f);
+            let bools = MultiVec::<2, _>::from_fn(outer_size, sizes, f);
+            prop_assert_eq!(bits.shape(), bools.shape());
+            for i in 0..outer_size {
+                for j in 0..sizes[0] {
+                    for k in 0..sizes[1] {
+                        prop_assert_eq!(bits.get([i, j, k]), bools[i][j][k]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::vec;
+
+use ::bytemuck::Pod;
+
+use crate::{len_of, offsets_of, Data, DataMut, MultiVec, MultiVecBase, ShapeError};
+
+impl<D: Data<T>, const N: usize, T: Pod> MultiVecBase<D, N, T> {
+    pub fn as_bytes(&self) -> &[u8] {
+        ::bytemuck::cast_slice(self.slice())
+    }
+}
+
+impl<D: DataMut<T>, const N: usize, T: Pod> MultiVecBase<D, N, T> {
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        ::bytemuck::cast_slice_mut(self.slice_mut())
+    }
+}
+
+impl<const N: usize, T: Pod> MultiVec<N, T> {
+    pub fn from_bytes(
+        outer_size: usize,
+        sizes: [usize; N],
+        bytes: &[u8],
+    ) -> Result<Self, ShapeError> {
+        let element_size = size_of::<T>();
+        // Only an empty slice is a multiple of a zero element size.
+        if bytes.len().checked_rem(element_size).unwrap_or(bytes.len()) != 0 {
+            return Err(ShapeError::ByteLength {
+                len: bytes.len(),
+                element_size,
+            });
+        }
+
+        let offsets = offsets_of(sizes)?;
+        let expected = len_of(outer_size, &offsets)?;
+        if element_size == 0 {
+            return Ok(Self::from_parts(
+                vec![T::zeroed(); expected],
+                outer_size,
+                sizes,
+            ));
+        }
+        let actual = bytes.len() / element_size;
+        if actual != expected {
+            return Err(ShapeError::ElementCount { expected, actual });
+        }
+
+        Ok(Self::from_parts(
+            ::bytemuck::pod_collect_to_vec(bytes),
+            outer_size,
+            sizes,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MultiVec, ShapeError};
+
+    #[test]
+    fn test_as_bytes() {
+        let mut m = MultiVec::<1, _>::from_fn(2, [2], |i, [j]| (i * 2 + j) as u16);
+        assert_eq!(
+            m.as_bytes(),
+            [0u16, 1, 2, 3].map(u16::to_ne_bytes).as_flattened()
+        );
+        assert_eq!(
+            m.row(1).as_bytes(),
+            [2u16, 3].map(u16::to_ne_bytes).as_flattened()
+        );
+
+        m.row_mut(1).as_bytes_mut().copy_from_slice(&[0xff; 4]);
+        assert_eq!(m[1][0], 0xffff);
+        assert_eq!(m[0][1], 1);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as u32);
+        assert_eq!(MultiVec::from_bytes(2, [3], m.as_bytes()), Ok(m.clone()));
+        assert_eq!(
+            MultiVec::<1, u32>::from_bytes(2, [3], &m.as_bytes()[1..]),
+            Err(ShapeError::ByteLength {
+                len: 23,
+                element_size: 4
+            })
+        );
+        assert_eq!(
+            MultiVec::<1, u32>::from_bytes(3, [3], m.as_bytes()),
+            Err(ShapeError::ElementCount {
+                expected: 9,
+                actual: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_zero_sized() {
+        let m = MultiVec::<1, ()>::from_bytes(2, [3], &[]).unwrap();
+        assert_eq!(m.shape(), [2, 3]);
+        assert_eq!(m.iter().count(), 6);
+        assert!(m.as_bytes().is_empty());
+        assert_eq!(
+            MultiVec::<1, ()>::from_bytes(2, [3], &[0]),
+            Err(ShapeError::ByteLength {
+                len: 1,
+                element_size: 0
+            })
+        );
+    }
+}
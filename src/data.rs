@@ -0,0 +1,57 @@
+use alloc::vec::Vec;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub(crate) use sealed::Sealed;
+
+/// Storage that a [`MultiVecBase`](crate::MultiVecBase) reads its elements from.
+///
+/// This is implemented for `Vec<T>` and, with the `smallvec` feature, `SmallVec<[T; CAP]>`,
+/// which own the elements, and for `&[T]` and `&mut [T]`, which borrow them from another grid.
+/// It is sealed, so it cannot be implemented outside this crate.
+pub trait Data<T>: Sealed {
+    fn slice(&self) -> &[T];
+}
+
+/// Storage that a [`MultiVecBase`](crate::MultiVecBase) can also mutate its elements through.
+pub trait DataMut<T>: Data<T> {
+    fn slice_mut(&mut self) -> &mut [T];
+}
+
+impl<T> Sealed for Vec<T> {}
+
+impl<T> Data<T> for Vec<T> {
+    fn slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> DataMut<T> for Vec<T> {
+    fn slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> Sealed for &[T] {}
+
+impl<T> Data<T> for &[T] {
+    fn slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> Sealed for &mut [T] {}
+
+impl<T> Data<T> for &mut [T] {
+    fn slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> DataMut<T> for &mut [T] {
+    fn slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
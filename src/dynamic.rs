@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::Debug,
+    ops::{Index, IndexMut},
+};
+
+use crate::{DebugSlice, MultiVec, ShapeError};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct DynMultiVec<T> {
+    inner: Vec<T>,
+    shape: Vec<usize>,
+    offsets: Vec<usize>,
+}
+
+impl<T: Debug> Debug for DynMultiVec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let slice = DebugSlice::new(f, &self.inner, &self.shape, &self.offsets);
+        f.debug_tuple("DynMultiVec").field(&slice).finish()
+    }
+}
+
+impl<const N: usize, T> MultiVec<N, T> {
+    pub fn into_dyn(self) -> DynMultiVec<T> {
+        DynMultiVec {
+            shape: self.shape_vec(),
+            inner: self.inner,
+            offsets: self.offsets.into(),
+        }
+    }
+}
+
+impl<T> DynMultiVec<T> {
+    pub fn try_into_static<const N: usize>(self) -> Result<MultiVec<N, T>, ShapeError> {
+        let (outer_size, sizes) = self.shape.split_first().unwrap();
+        let sizes = sizes.try_into().map_err(|_| ShapeError::Rank {
+            expected: N + 1,
+            actual: self.shape.len(),
+        })?;
+        Ok(MultiVec::from_parts(self.inner, *outer_size, sizes))
+    }
+
+    pub fn rank(&self) -> usize {
+        self.offsets.len() + 1
+    }
+
+    pub fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+
+    /// Changes the shape, and possibly the rank, keeping the elements in the same order.
+    pub fn reshape(&mut self, new_shape: &[usize]) -> Result<(), ShapeError> {
+        let mismatch = |d: &Self| ShapeError::Mismatch {
+            left: d.shape(),
+            right: new_shape.to_vec(),
+        };
+        let Some((outer, sizes)) = new_shape.split_first() else {
+            return Err(mismatch(self));
+        };
+        let mut prod = Some(1usize);
+        let mut offsets: Vec<usize> = sizes
+            .iter()
+            .rev()
+            .map(|n| {
+                prod = prod.and_then(|prod| prod.checked_mul(*n));
+                prod.unwrap_or(0)
+            })
+            .collect();
+        let stride = prod.ok_or(ShapeError::Overflow)?;
+        if stride.checked_mul(*outer) != Some(self.inner.len()) {
+            return Err(mismatch(self));
+        }
+        offsets.reverse();
+        self.shape = new_shape.to_vec();
+        self.offsets = offsets;
+        Ok(())
+    }
+
+    pub fn as_flat_slice(&self) -> &[T] {
+        &self.inner
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.inner.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.inner.iter_mut()
+    }
+
+    fn flat_index(&self, coords: &[usize]) -> usize {
+        let rank = self.rank();
+        assert!(
+            coords.len() == rank,
+            "got {} coordinates for rank {rank}",
+            coords.len()
+        );
+        for (axis, (coord, extent)) in coords.iter().zip(&self.shape).enumerate() {
+            assert!(
+                coord < extent,
+                "index {coord} is out of range for dimension {axis} of extent {extent}"
+            );
+        }
+        coords
+            .iter()
+            .zip(self.offsets.iter().chain([&1]))
+            .map(|(coord, offset)| coord * offset)
+            .sum()
+    }
+}
+
+impl<T> Index<&[usize]> for DynMultiVec<T> {
+    type Output = T;
+
+    fn index(&self, coords: &[usize]) -> &Self::Output {
+        &self.inner[self.flat_index(coords)]
+    }
+}
+
+impl<T> IndexMut<&[usize]> for DynMultiVec<T> {
+    fn index_mut(&mut self, coords: &[usize]) -> &mut Self::Output {
+        let index = self.flat_index(coords);
+        &mut self.inner[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DynMultiVec, MultiVec, ShapeError};
+
+    #[test]
+    fn test_into_dyn() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let mut d = m.clone().into_dyn();
+        assert_eq!(d.rank(), 3);
+        assert_eq!(d.shape(), [2, 3, 4]);
+        assert_eq!(d[&[1, 2, 3][..]], (1, 2, 3));
+        assert!(d.iter().eq(m.iter()));
+        assert_eq!(
+            format!("{d:?}"),
+            format!("{m:?}").replace("MultiVec<2>", "DynMultiVec")
+        );
+
+        d[&[0, 1, 2][..]] = (9, 9, 9);
+        let m = d.try_into_static::<2>().unwrap();
+        assert_eq!(m[0][1][2], (9, 9, 9));
+
+        let m = MultiVec::<2, u8>::filled_default(3, [0, 5]);
+        let d = m.clone().into_dyn();
+        assert_eq!(d.shape(), [3, 0, 5]);
+        assert_eq!(d.try_into_static::<2>(), Ok(m));
+    }
+
+    #[test]
+    fn test_try_into_static_rank() {
+        let d = MultiVec::<1, u8>::filled_default(2, [3]).into_dyn();
+        assert_eq!(
+            d.clone().try_into_static::<2>(),
+            Err(ShapeError::Rank {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            d.try_into_static::<0>(),
+            Err(ShapeError::Rank {
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_heterogeneous_ranks() {
+        let grids: Vec<DynMultiVec<u8>> = vec![
+            MultiVec::<0, _>::filled_default(3, []).into_dyn(),
+            MultiVec::<1, _>::filled_default(2, [3]).into_dyn(),
+            MultiVec::<3, _>::filled_default(1, [2, 3, 4]).into_dyn(),
+        ];
+        let ranks: Vec<_> = grids.iter().map(DynMultiVec::rank).collect();
+        assert_eq!(ranks, [1, 2, 4]);
+    }
+
+    #[test]
+    fn test_reshape() {
+        let m = MultiVec::<1, _>::from_fn(2, [6], |i, [j]| i * 6 + j);
+        let mut d = m.clone().into_dyn();
+        d.reshape(&[3, 2, 2]).unwrap();
+        assert_eq!(d.rank(), 3);
+        assert_eq!(d.shape(), [3, 2, 2]);
+        assert_eq!(d[&[2, 0, 1][..]], 9);
+        assert!(d.iter().eq(m.iter()));
+
+        d.reshape(&[12]).unwrap();
+        assert_eq!(d.try_into_static::<0>().unwrap()[11], 11);
+
+        let mut d = m.into_dyn();
+        assert_eq!(
+            d.reshape(&[5, 2]),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 6],
+                right: vec![5, 2]
+            })
+        );
+        assert_eq!(
+            d.reshape(&[]),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 6],
+                right: vec![]
+            })
+        );
+        assert_eq!(d.reshape(&[1, usize::MAX, 2]), Err(ShapeError::Overflow));
+        assert_eq!(d.shape(), [2, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "got 2 coordinates for rank 3")]
+    fn test_index_wrong_rank() {
+        let d = MultiVec::<2, u8>::filled_default(2, [3, 4]).into_dyn();
+        _ = d[&[0, 0][..]];
+    }
+}
@@ -0,0 +1,65 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShapeError {
+    RowLength {
+        expected: usize,
+        actual: usize,
+    },
+    RowWidth {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    Rank {
+        expected: usize,
+        actual: usize,
+    },
+    ElementCount {
+        expected: usize,
+        actual: usize,
+    },
+    ByteLength {
+        len: usize,
+        element_size: usize,
+    },
+    Overflow,
+    Mismatch {
+        left: Vec<usize>,
+        right: Vec<usize>,
+    },
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RowLength { expected, actual } => {
+                write!(f, "row has {actual} elements, expected {expected}")
+            }
+            Self::RowWidth {
+                row,
+                expected,
+                actual,
+            } => write!(f, "row {row} has {actual} elements, expected {expected}"),
+            Self::Rank { expected, actual } => {
+                write!(f, "rank is {actual}, expected {expected}")
+            }
+            Self::ElementCount { expected, actual } => {
+                write!(f, "got {actual} elements, expected {expected}")
+            }
+            Self::ByteLength { len, element_size } => {
+                write!(
+                    f,
+                    "{len} bytes is not a multiple of the element size {element_size}"
+                )
+            }
+            Self::Overflow => write!(f, "shape is too large"),
+            Self::Mismatch { left, right } => {
+                write!(f, "shapes {left:?} and {right:?} are incompatible")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ShapeError {}
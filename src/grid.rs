@@ -0,0 +1,581 @@
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{Display, Write},
+    ops::Range,
+};
+
+use crate::{len_of, offsets_of, Data, MultiVec, MultiVecBase, MultiVecRef, ShapeError};
+
+impl MultiVec<1, char> {
+    pub fn from_lines(s: &str) -> Result<Self, ShapeError> {
+        let mut inner = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (row, line) in s.lines().enumerate() {
+            let start = inner.len();
+            inner.extend(line.chars());
+            let actual = inner.len() - start;
+            let expected = *width.get_or_insert(actual);
+            if actual != expected {
+                return Err(ShapeError::RowWidth {
+                    row,
+                    expected,
+                    actual,
+                });
+            }
+            height += 1;
+        }
+        Ok(Self::from_parts(inner, height, [width.unwrap_or(0)]))
+    }
+}
+
+impl<T> MultiVec<1, T> {
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, ShapeError> {
+        let mut inner = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (row, values) in rows.into_iter().enumerate() {
+            let actual = values.len();
+            let expected = *width.get_or_insert(actual);
+            if actual != expected {
+                return Err(ShapeError::RowWidth {
+                    row,
+                    expected,
+                    actual,
+                });
+            }
+            inner.extend(values);
+            height += 1;
+        }
+        Ok(Self::from_parts(inner, height, [width.unwrap_or(0)]))
+    }
+
+    pub fn map_rows<U>(
+        &self,
+        f: impl Fn(MultiVecRef<'_, 0, T>) -> Vec<U>,
+    ) -> Result<MultiVec<1, U>, ShapeError> {
+        MultiVec::from_rows((0..self.size()).map(|i| f(self.row(i))))
+    }
+
+    /// Stably reorders the rows by the key extracted from each of them.
+    pub fn sort_rows_by_key<K: Ord>(&mut self, mut key: impl FnMut(MultiVecRef<'_, 0, T>) -> K) {
+        let keys: Vec<K> = (0..self.size()).map(|i| key(self.row(i))).collect();
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|a, b| keys[*a].cmp(&keys[*b]));
+        let width = self.stride();
+        for i in 0..order.len() {
+            let mut j = order[i];
+            while j < i {
+                j = order[j];
+            }
+            if j != i {
+                let (front, back) = self.inner.split_at_mut(j * width);
+                front[i * width..(i + 1) * width].swap_with_slice(&mut back[..width]);
+            }
+        }
+    }
+
+    /// Yields copies of every `h` × `w` block of the grid, row by row from the top-left.
+    pub fn windows_2d(&self, h: usize, w: usize) -> impl Iterator<Item = MultiVec<1, T>> + '_
+    where
+        T: Clone,
+    {
+        assert!(h != 0 && w != 0, "window size must be non-zero");
+        let [height, width] = self.shape();
+        let (rows, cols) = (
+            (height + 1).saturating_sub(h),
+            (width + 1).saturating_sub(w),
+        );
+        (0..rows).flat_map(move |i| {
+            (0..cols).map(move |j| {
+                MultiVec::from_fn(h, [w], |di, [dj]| {
+                    self.inner[(i + di) * width + j + dj].clone()
+                })
+            })
+        })
+    }
+
+    /// Draws the grid as one line per row, mapping each cell to a character. This is the inverse
+    /// of [`from_lines`](MultiVec::from_lines).
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        self.render_with(cell)
+    }
+
+    /// Like [`render`](Self::render), but each cell may take any number of characters.
+    pub fn render_with<D: Display>(&self, cell: impl Fn(&T) -> D) -> String {
+        let mut out = String::new();
+        for i in 0..self.size() {
+            if i > 0 {
+                out.push('\n');
+            }
+            for v in self.row(i).iter() {
+                write!(out, "{}", cell(v)).unwrap();
+            }
+        }
+        out
+    }
+
+    pub fn count_rows(&self, pred: impl Fn(MultiVecRef<'_, 0, T>) -> bool) -> usize {
+        self.rows().map(pred).filter(|matched| *matched).count()
+    }
+
+    pub fn for_each_adjacent_rows(
+        &self,
+        mut f: impl FnMut(MultiVecRef<'_, 0, T>, MultiVecRef<'_, 0, T>),
+    ) {
+        for i in 1..self.size() {
+            f(self.row(i - 1), self.row(i));
+        }
+    }
+
+    /// Sets every cell in `rows` × `cols` to `value`. Panics if either range extends past the
+    /// grid.
+    pub fn fill_region(&mut self, rows: Range<usize>, cols: Range<usize>, value: T)
+    where
+        T: Clone,
+    {
+        let [height, width] = self.shape();
+        assert!(
+            rows.start <= rows.end && rows.end <= height,
+            "rows {rows:?} are out of range for height {height}"
+        );
+        assert!(
+            cols.start <= cols.end && cols.end <= width,
+            "columns {cols:?} are out of range for width {width}"
+        );
+        for i in rows {
+            self.inner[i * width + cols.start..i * width + cols.end].fill(value.clone());
+        }
+    }
+
+    /// Copies `src` onto the grid with its top-left cell at `(at_row, at_col)`. Cells that would
+    /// land outside the grid are skipped.
+    pub fn blit(&mut self, src: &MultiVec<1, T>, at_row: usize, at_col: usize)
+    where
+        T: Clone,
+    {
+        let [height, width] = self.shape();
+        let [src_height, src_width] = src.shape();
+        if at_row >= height || at_col >= width {
+            return;
+        }
+        let rows = src_height.min(height - at_row);
+        let cols = src_width.min(width - at_col);
+        if rows == 0 || cols == 0 {
+            return;
+        }
+        for i in 0..rows {
+            let start = (at_row + i)
+                .checked_mul(width)
+                .and_then(|start| start.checked_add(at_col))
+                .unwrap_or_else(|| panic!("{}", ShapeError::Overflow));
+            self.inner[start..start + cols].clone_from_slice(&src.inner[i * src_width..][..cols]);
+        }
+    }
+
+    /// Resizes the grid to `new_outer` × `new_inner`, keeping the values in the region the old
+    /// and new shapes share and filling every new cell with `fill`.
+    pub fn resize_grid(&mut self, new_outer: usize, new_inner: usize, fill: T)
+    where
+        T: Clone,
+    {
+        let [height, width] = self.shape();
+        let offsets = offsets_of([new_inner]).unwrap_or_else(|e| panic!("{e}"));
+        let len = len_of(new_outer, &offsets).unwrap_or_else(|e| panic!("{e}"));
+        let kept = width.min(new_inner);
+        let mut old = core::mem::take(&mut self.inner).into_iter();
+        let mut inner = Vec::with_capacity(len);
+        for _ in 0..height.min(new_outer) {
+            inner.extend(old.by_ref().take(kept));
+            inner.resize(inner.len() + new_inner - kept, fill.clone());
+            old.by_ref().take(width - kept).for_each(drop);
+        }
+        inner.resize(len, fill);
+        *self = Self::from_parts(inner, new_outer, [new_inner]);
+    }
+
+    /// Returns a copy of the grid surrounded by the given number of `fill` rows and columns on
+    /// each side.
+    pub fn pad(&self, top: usize, bottom: usize, left: usize, right: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let [height, width] = self.shape();
+        let new_width = width
+            .checked_add(left)
+            .and_then(|w| w.checked_add(right))
+            .unwrap_or_else(|| panic!("{}", ShapeError::Overflow));
+        let new_height = height
+            .checked_add(top)
+            .and_then(|h| h.checked_add(bottom))
+            .unwrap_or_else(|| panic!("{}", ShapeError::Overflow));
+        let offsets = offsets_of([new_width]).unwrap_or_else(|e| panic!("{e}"));
+        let len = len_of(new_height, &offsets).unwrap_or_else(|e| panic!("{e}"));
+        let mut inner = Vec::with_capacity(len);
+        inner.resize(top * new_width, fill.clone());
+        for row in self.inner.chunks_exact(width.max(1)) {
+            inner.resize(inner.len() + left, fill.clone());
+            inner.extend_from_slice(row);
+            inner.resize(inner.len() + right, fill.clone());
+        }
+        inner.resize(len, fill);
+        Self::from_parts(inner, new_height, [new_width])
+    }
+}
+
+const ORTHOGONAL: [[isize; 2]; 4] = [[-1, 0], [0, -1], [0, 1], [1, 0]];
+const DIAGONAL: [[isize; 2]; 8] = [
+    [-1, -1],
+    [-1, 0],
+    [-1, 1],
+    [0, -1],
+    [0, 1],
+    [1, -1],
+    [1, 0],
+    [1, 1],
+];
+
+impl<D: Data<T>, T> MultiVecBase<D, 1, T> {
+    pub fn iter_column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        self.iter_axis(1, col)
+    }
+
+    fn neighbors_by<'a>(
+        &'a self,
+        [i, j]: [usize; 2],
+        deltas: &'static [[isize; 2]],
+    ) -> impl Iterator<Item = ([usize; 2], &'a T)> + 'a {
+        let [rows, cols] = self.shape();
+        deltas.iter().filter_map(move |[di, dj]| {
+            let i = i.checked_add_signed(*di).filter(|i| *i < rows)?;
+            let j = j.checked_add_signed(*dj).filter(|j| *j < cols)?;
+            Some(([i, j], &self.slice()[i * cols + j]))
+        })
+    }
+
+    pub fn neighbors(&self, coord: [usize; 2]) -> impl Iterator<Item = ([usize; 2], &T)> + '_ {
+        self.neighbors_by(coord, &ORTHOGONAL)
+    }
+
+    pub fn neighbors_diagonal(
+        &self,
+        coord: [usize; 2],
+    ) -> impl Iterator<Item = ([usize; 2], &T)> + '_ {
+        self.neighbors_by(coord, &DIAGONAL)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{MultiVec, MultiVecRef, ShapeError};
+
+    #[test]
+    fn test_from_lines() {
+        let m = MultiVec::from_lines("#..\n.#.\r\n..#\n").unwrap();
+        assert_eq!(m.shape(), [3, 3]);
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(3, [3], |i, [j]| if i == j { '#' } else { '.' })
+        );
+
+        assert_eq!(MultiVec::from_lines("").unwrap().shape(), [0, 0]);
+        assert_eq!(
+            MultiVec::from_lines("ab\ncd\nefg\nh"),
+            Err(ShapeError::RowWidth {
+                row: 2,
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let m = MultiVec::from_rows([vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(m, MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j + 1));
+
+        let empty = MultiVec::<1, u8>::from_rows([]).unwrap();
+        assert_eq!(empty.shape(), [0, 0]);
+        assert!(empty.is_empty());
+
+        let blank = MultiVec::<1, u8>::from_rows([vec![], vec![]]).unwrap();
+        assert_eq!(blank.shape(), [2, 0]);
+        assert_eq!(MultiVec::from_lines("\n\n\n").unwrap().shape(), [3, 0]);
+
+        assert_eq!(
+            MultiVec::from_rows([vec![1, 2], vec![3, 4], vec![5]]),
+            Err(ShapeError::RowWidth {
+                row: 2,
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_map_rows() {
+        let m = MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (i * 2 + j) as i32);
+        let sums = m.map_rows(|row| vec![row.iter().sum::<i32>(); 3]).unwrap();
+        assert_eq!(sums.shape(), [3, 3]);
+        assert_eq!(sums[2][0], 9);
+
+        let normalized = m.map_rows(|row| row.iter().map(|v| v - m[[0, 0]] - row[0]).collect());
+        assert_eq!(
+            normalized.unwrap(),
+            MultiVec::<1, _>::from_fn(3, [2], |_, [j]| j as i32)
+        );
+
+        assert_eq!(
+            m.map_rows(|row| vec![0; row[0] as usize]),
+            Err(ShapeError::RowWidth {
+                row: 1,
+                expected: 0,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i == j);
+        let s = m.render(|v| if *v { '#' } else { '.' });
+        assert_eq!(s, "#..\n.#.");
+        assert_eq!(s.lines().count(), m.size());
+        assert!(s.lines().all(|line| line.len() == 3));
+        assert_eq!(MultiVec::from_lines(&s).unwrap().render(|c| *c), s);
+
+        let m = MultiVec::<1, _>::from_fn(2, [2], |i, [j]| i * 10 + j);
+        assert_eq!(m.render_with(|v| format!("{v:>3}")), "  0  1\n 10 11");
+        assert_eq!(MultiVec::<1, u8>::default().render(|_| '?'), "");
+    }
+
+    #[test]
+    fn test_count_rows() {
+        let m = MultiVec::from_rows([vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1], vec![0, 0, 0]])
+            .unwrap();
+        assert_eq!(m.count_rows(|row| row.all(|v| *v == 1)), 2);
+        assert_eq!(m.count_rows(|row| row.any(|v| *v == 0)), 2);
+        assert_eq!(m.count_rows(|_| true), m.size());
+        assert_eq!(m.count_rows(|row| row.same_shape(&m.row(0))), m.size());
+        assert_eq!(MultiVec::<1, u8>::default().count_rows(|_| true), 0);
+    }
+
+    #[test]
+    fn test_iter_column() {
+        let m = MultiVec::<1, _>::from_fn(4, [3], |i, [j]| i * 3 + j);
+        assert_eq!(m.iter_column(1).collect::<Vec<_>>(), [&1, &4, &7, &10]);
+        assert_eq!(m.iter_column(2).count(), m.size());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_iter_column_out_of_range() {
+        _ = MultiVec::<1, u8>::filled_default(4, [3]).iter_column(3);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| [i, j]);
+        assert!(m.neighbors_diagonal([1, 1]).all(|(c, v)| c == *v));
+
+        let coords = |it: Vec<([usize; 2], _)>| it.into_iter().map(|(c, _)| c).collect::<Vec<_>>();
+        assert_eq!(
+            coords(m.neighbors([1, 1]).collect()),
+            [[0, 1], [1, 0], [1, 2], [2, 1]]
+        );
+        assert_eq!(coords(m.neighbors([0, 0]).collect()), [[0, 1], [1, 0]]);
+        assert_eq!(coords(m.neighbors([2, 3]).collect()), [[1, 3], [2, 2]]);
+        assert_eq!(m.neighbors_diagonal([1, 1]).count(), 8);
+        assert_eq!(
+            coords(m.neighbors_diagonal([0, 3]).collect()),
+            [[0, 2], [1, 2], [1, 3]]
+        );
+        assert_eq!(MultiVec::<1, u8>::default().neighbors([0, 0]).count(), 0);
+    }
+
+    #[test]
+    fn test_fill_region() {
+        let mut m = MultiVec::<1, _>::filled_default(4, [5]);
+        m.fill_region(1..3, 2..5, 1);
+        m.fill_region(0..4, 0..0, 2);
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(4, [5], |i, [j]| ((1..3).contains(&i) && j >= 2) as i32)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "columns 3..6 are out of range for width 5")]
+    fn test_fill_region_out_of_range() {
+        MultiVec::<1, u8>::filled_default(4, [5]).fill_region(0..1, 3..6, 1);
+    }
+
+    #[test]
+    fn test_blit() {
+        let src = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j + 1);
+        let mut m = MultiVec::<1, usize>::filled_default(2, [3]);
+        m.blit(&src, 0, 0);
+        assert_eq!(m, src);
+
+        let mut m = MultiVec::<1, usize>::filled_default(3, [4]);
+        m.blit(&src, 2, 2);
+        assert_eq!(m.as_flat_slice(), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+        m.blit(&src, 0, 1);
+        assert_eq!(m.as_flat_slice(), [0, 1, 2, 3, 0, 4, 5, 6, 0, 0, 1, 2]);
+        m.blit(&src, 3, 0);
+        m.blit(&src, 0, 4);
+        m.blit(&src, usize::MAX, usize::MAX);
+        assert_eq!(m.as_flat_slice(), [0, 1, 2, 3, 0, 4, 5, 6, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_blit_past_right_edge() {
+        let src = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j + 1);
+        let mut m = MultiVec::<1, usize>::filled_default(3, [4]);
+        m.blit(&src, 0, 5);
+        m.blit(&src, 1, 9);
+        m.blit(&src, 0, usize::MAX);
+        m.blit(&src, 2, usize::MAX);
+        assert!(m.iter().all(|v| *v == 0));
+
+        m.blit(&MultiVec::default(), 1, 1);
+        m.blit(&MultiVec::<1, usize>::filled_default(2, [0]), 1, 1);
+        assert!(m.iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_resize_grid() {
+        let f = |i: usize, [j]: [usize; 1]| (i * 10 + j) as i32;
+        let mut m = MultiVec::<1, _>::from_fn(3, [4], f);
+        m.resize_grid(4, 2, -1);
+        assert_eq!(m.shape(), [4, 2]);
+        assert_eq!(m.as_flat_slice(), [0, 1, 10, 11, 20, 21, -1, -1]);
+
+        m.resize_grid(2, 3, 7);
+        assert_eq!(m.as_flat_slice(), [0, 1, 7, 10, 11, 7]);
+
+        m.resize_grid(0, 5, 0);
+        assert_eq!(m.shape(), [0, 5]);
+    }
+
+    #[test]
+    fn test_pad() {
+        let m = MultiVec::<1, _>::from_fn(2, [2], |i, [j]| i * 2 + j + 1);
+        let padded = m.pad(1, 0, 2, 1, 0);
+        assert_eq!(padded.shape(), [3, 5]);
+        assert_eq!(
+            padded.as_flat_slice(),
+            [0, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 3, 4, 0]
+        );
+        assert_eq!(m.pad(0, 0, 0, 0, 9), m);
+        assert_eq!(
+            m.pad(0, 2, 0, 0, 9).as_flat_slice(),
+            [1, 2, 3, 4, 9, 9, 9, 9]
+        );
+
+        let empty = MultiVec::<1, usize>::filled_default(0, [2]);
+        assert_eq!(empty.pad(1, 1, 0, 0, 5).as_flat_slice(), [5; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "shape is too large")]
+    fn test_pad_overflow() {
+        MultiVec::<1, u8>::filled_default(1, [1]).pad(0, 0, usize::MAX, 0, 0);
+    }
+
+    #[test]
+    fn test_sort_rows_by_key() {
+        let mut m =
+            MultiVec::from_rows([vec![3, 0, 1], vec![1, 1, 1], vec![2, 2, 2], vec![1, 3, 0]])
+                .unwrap();
+        m.sort_rows_by_key(|row: MultiVecRef<'_, 0, _>| row[0]);
+        assert_eq!(m.as_flat_slice(), [1, 1, 1, 1, 3, 0, 2, 2, 2, 3, 0, 1]);
+        m.sort_rows_by_key(|row| core::cmp::Reverse(row[2]));
+        assert_eq!(m.as_flat_slice(), [2, 2, 2, 1, 1, 1, 3, 0, 1, 1, 3, 0]);
+        m.sort_rows_by_key(|_| 0);
+        assert_eq!(m.as_flat_slice(), [2, 2, 2, 1, 1, 1, 3, 0, 1, 1, 3, 0]);
+    }
+
+    #[test]
+    fn test_windows_2d() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * 4 + j);
+        let windows: Vec<_> = m.windows_2d(2, 3).collect();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[0].as_flat_slice(), [0, 1, 2, 4, 5, 6]);
+        assert_eq!(windows[1].as_flat_slice(), [1, 2, 3, 5, 6, 7]);
+        assert_eq!(windows[3].as_flat_slice(), [5, 6, 7, 9, 10, 11]);
+        assert_eq!(m.windows_2d(3, 4).next(), Some(m.clone()));
+        assert_eq!(m.windows_2d(4, 1).count(), 0);
+        assert_eq!(m.windows_2d(1, 5).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn test_windows_2d_zero() {
+        _ = MultiVec::<1, u8>::filled_default(3, [3]).windows_2d(0, 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_sort_rows_by_key_matches_vec(keys in prop::collection::vec(0..4u8, 0..10)) {
+            let mut m = MultiVec::<1, _>::from_fn(keys.len(), [2], |i, [j]| (keys[i], i, j));
+            m.sort_rows_by_key(|row| row[0].0);
+            let mut rows: Vec<_> = (0..keys.len()).map(|i| (keys[i], i)).collect();
+            rows.sort_by_key(|row| row.0);
+            for (i, (key, original)) in rows.into_iter().enumerate() {
+                prop_assert_eq!(m[i][0], (key, original, 0));
+                prop_assert_eq!(m[i][1], (key, original, 1));
+            }
+        }
+
+        #[test]
+        fn test_windows_2d_count(
+            [height, width] in prop::array::uniform2(1..7usize),
+            [h, w] in prop::array::uniform2(1..7usize),
+        ) {
+            let m = MultiVec::<1, _>::from_fn(height, [width], |i, [j]| (i, j));
+            let expected = (height + 1).saturating_sub(h) * (width + 1).saturating_sub(w);
+            prop_assert_eq!(m.windows_2d(h, w).count(), expected);
+            for window in m.windows_2d(h, w) {
+                prop_assert_eq!(window.shape(), [h, w]);
+                let (i, j) = window[0][0];
+                prop_assert!(window.indexed_iter().all(|([di, dj], v)| *v == (i + di, j + dj)));
+            }
+        }
+
+        #[test]
+        fn test_resize_grid_preserves(
+            [height, width, new_outer, new_inner] in prop::array::uniform4(0..6usize),
+        ) {
+            let f = |i: usize, [j]: [usize; 1]| (i * 10 + j) as i32;
+            let mut m = MultiVec::<1, _>::from_fn(height, [width], f);
+            m.resize_grid(new_outer, new_inner, -1);
+            prop_assert_eq!(m.shape()[1], new_inner);
+            prop_assert_eq!(m.as_flat_slice().len(), new_outer * new_inner);
+            for i in 0..new_outer {
+                for j in 0..new_inner {
+                    let expected = if i < height && j < width { f(i, [j]) } else { -1 };
+                    prop_assert_eq!(m[i][j], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_each_adjacent_rows() {
+        let m = MultiVec::<1, _>::from_fn(4, [3], |i, [j]| i * i + j);
+        let mut diffs = Vec::new();
+        m.for_each_adjacent_rows(|a, b| {
+            diffs.push((0..3).map(|j| b[j] - a[j]).collect::<Vec<_>>());
+            assert!(a.same_shape(&m.row(0)));
+        });
+        assert_eq!(diffs, [[1; 3], [3; 3], [5; 3]]);
+
+        let mut calls = 0;
+        MultiVec::<1, u8>::filled_default(1, [3]).for_each_adjacent_rows(|_, _| calls += 1);
+        MultiVec::<1, u8>::default().for_each_adjacent_rows(|_, _| calls += 1);
+        assert_eq!(calls, 0);
+    }
+}
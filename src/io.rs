@@ -0,0 +1,128 @@
+use std::io::{self, Read, Write};
+
+use ::bytemuck::Pod;
+
+use crate::{len_of, offsets_of, MultiVec, ShapeError};
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<usize> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    u64::from_le_bytes(bytes).try_into().map_err(invalid_data)
+}
+
+impl<const N: usize, T: Pod> MultiVec<N, T> {
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&(N as u64 + 1).to_le_bytes())?;
+        for size in self.shape_vec() {
+            w.write_all(&(size as u64).to_le_bytes())?;
+        }
+        w.write_all(::bytemuck::cast_slice(&self.inner))
+    }
+
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let rank = read_u64(r)?;
+        if rank != N + 1 {
+            return Err(invalid_data(format!("rank is {rank}, expected {}", N + 1)));
+        }
+        let outer_size = read_u64(r)?;
+        let mut sizes = [0; N];
+        for size in &mut sizes {
+            *size = read_u64(r)?;
+        }
+
+        let len = offsets_of(sizes)
+            .and_then(|offsets| len_of(outer_size, &offsets))
+            .and_then(|len| len.checked_mul(size_of::<T>()).ok_or(ShapeError::Overflow))
+            .map_err(invalid_data)?;
+        // A corrupt header must not allocate more than the reader actually holds.
+        let mut bytes = Vec::new();
+        r.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("payload is {} bytes, expected {len}", bytes.len()),
+            ));
+        }
+        Self::from_bytes(outer_size, sizes, &bytes).map_err(invalid_data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::ErrorKind;
+
+    use crate::{MultiVec, ShapeError};
+
+    #[test]
+    fn test_round_trip() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i * 12 + j * 4 + k) as u16);
+        let mut buf = Vec::new();
+        m.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), 4 * 8 + 24 * 2);
+        assert_eq!(buf[..16], [3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(MultiVec::read_from(&mut buf.as_slice()).unwrap(), m);
+    }
+
+    #[test]
+    fn test_read_from_leaves_rest() {
+        let a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as u8);
+        let b = MultiVec::<1, _>::from_fn(1, [2], |_, [j]| j as u8);
+        let mut buf = Vec::new();
+        a.write_to(&mut buf).unwrap();
+        b.write_to(&mut buf).unwrap();
+
+        let mut r = buf.as_slice();
+        assert_eq!(MultiVec::read_from(&mut r).unwrap(), a);
+        assert_eq!(MultiVec::read_from(&mut r).unwrap(), b);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn test_read_from_huge_header() {
+        let header: Vec<u8> = [2u64, 1 << 40, 1 << 20]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let error = MultiVec::<1, u8>::read_from(&mut header.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_round_trip_zero_extent() {
+        let m = MultiVec::<2, u16>::filled_default(3, [0, 5]);
+        let mut buf = Vec::new();
+        m.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), 4 * 8);
+        assert_eq!(buf[8..16], [3, 0, 0, 0, 0, 0, 0, 0]);
+        let read = MultiVec::<2, u16>::read_from(&mut buf.as_slice()).unwrap();
+        assert_eq!(read.shape(), [3, 0, 5]);
+        assert_eq!(read, m);
+    }
+
+    #[test]
+    fn test_read_from_invalid() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as u32);
+        let mut buf = Vec::new();
+        m.write_to(&mut buf).unwrap();
+
+        let error = MultiVec::<2, u32>::read_from(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let truncated = &buf[..buf.len() - 4];
+        let error = MultiVec::<1, u32>::read_from(&mut &truncated[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+        let mut header = buf[..24].to_vec();
+        header[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        let error = MultiVec::<1, u32>::read_from(&mut header.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), ShapeError::Overflow.to_string());
+
+        let error = MultiVec::<1, u32>::read_from(&mut &buf[..12]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
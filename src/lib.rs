@@ -1,311 +1,2656 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 mod array;
-use std::{
+mod bit;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+mod data;
+mod dynamic;
+mod error;
+mod grid;
+#[cfg(feature = "io")]
+mod io;
+mod matrix;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod ops;
+#[cfg(feature = "rayon")]
+mod rayon;
+mod row;
+#[cfg(feature = "smallvec")]
+mod small;
+mod transform;
+use alloc::{boxed::Box, format, vec::Vec};
+use core::{
+    cmp::Ordering,
     fmt::Debug,
-    ops::{Deref, DerefMut, Index, IndexMut},
-    sync::Mutex,
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{DerefMut, Index, IndexMut},
+};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+#[cfg(feature = "smallvec")]
+pub use crate::small::SmallMultiVec;
+use crate::{array::NonEmptyArrayExt, row::RowData};
+pub use crate::{
+    array::{NonEmpty, NonEmptyArray},
+    bit::BitMultiVec,
+    data::{Data, DataMut},
+    dynamic::DynMultiVec,
+    error::ShapeError,
+    row::Row,
 };
 
-use itertools::Itertools;
+unsafe fn drop_box<P>(ptr: *mut ()) {
+    _ = unsafe { Box::from_raw(ptr.cast::<P>()) };
+}
+
+type DropFn = unsafe fn(*mut ());
+
+struct Refs(Mutex<Vec<(*mut (), DropFn)>>);
+unsafe impl Send for Refs {}
+unsafe impl Sync for Refs {}
+
+impl Refs {
+    fn new() -> Self {
+        Self(Default::default())
+    }
+
+    fn lock(&self) -> impl DerefMut<Target = Vec<(*mut (), DropFn)>> + '_ {
+        #[cfg(feature = "std")]
+        return self.0.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        return self.0.lock();
+    }
+
+    fn add<P>(&self, value: P) -> *mut P {
+        let ptr = Box::into_raw(Box::new(value));
+        self.lock().push((ptr.cast(), drop_box::<P>));
+        ptr
+    }
+
+    fn clear(&mut self) {
+        for (ptr, drop_fn) in self.lock().drain(..) {
+            unsafe { drop_fn(ptr) };
+        }
+    }
+}
+
+impl Drop for Refs {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+const SUMMARY_THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+
+struct Ellipsis;
+
+impl Debug for Ellipsis {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("...")
+    }
+}
+
+/// Formats the elements from `start` on as nested lists of the given shape. `elements` only has
+/// to be indexable, so storage that can't lend out a slice can be formatted too.
+struct DebugSlice<'a, S: ?Sized> {
+    elements: &'a S,
+    start: usize,
+    shape: &'a [usize],
+    offsets: &'a [usize],
+    summarize: bool,
+}
+
+impl<'a, S: ?Sized> DebugSlice<'a, S> {
+    fn new(
+        f: &core::fmt::Formatter<'_>,
+        elements: &'a S,
+        shape: &'a [usize],
+        offsets: &'a [usize],
+    ) -> Self {
+        Self {
+            elements,
+            start: 0,
+            shape,
+            offsets,
+            summarize: !f.alternate() && shape.iter().product::<usize>() > SUMMARY_THRESHOLD,
+        }
+    }
+}
+
+impl<S: ?Sized + Index<usize>> Debug for DebugSlice<'_, S>
+where
+    S::Output: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (count, shape) = self.shape.split_first().unwrap();
+        let Some((offset, offsets)) = self.offsets.split_first() else {
+            return self.fmt_entries(f, *count, |i| &self.elements[self.start + i]);
+        };
+        self.fmt_entries(f, *count, |i| DebugSlice {
+            elements: self.elements,
+            start: self.start + i * offset,
+            shape,
+            offsets,
+            summarize: self.summarize,
+        })
+    }
+}
+
+impl<S: ?Sized> DebugSlice<'_, S> {
+    fn fmt_entries<E: Debug>(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        count: usize,
+        entry: impl Fn(usize) -> E,
+    ) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        if self.summarize && count > 2 * EDGE_ITEMS {
+            list.entries((0..EDGE_ITEMS).map(&entry));
+            list.entry(&Ellipsis);
+            list.entries((count - EDGE_ITEMS..count).map(&entry));
+        } else {
+            list.entries((0..count).map(&entry));
+        }
+        list.finish()
+    }
+}
+
+fn offsets_of<const N: usize>(mut sizes: [usize; N]) -> Result<[usize; N], ShapeError> {
+    let mut prod = Some(1usize);
+    sizes.reverse();
+    let mut offsets = sizes.map(|n| {
+        prod = prod.and_then(|prod| prod.checked_mul(n));
+        prod.unwrap_or(0)
+    });
+    prod.ok_or(ShapeError::Overflow)?;
+    offsets.reverse();
+    Ok(offsets)
+}
+
+fn len_of<const N: usize>(outer_size: usize, offsets: &[usize; N]) -> Result<usize, ShapeError> {
+    outer_size
+        .checked_mul(offsets.first().cloned().unwrap_or(1))
+        .ok_or(ShapeError::Overflow)
+}
+
+fn shape_of<const N: usize, const N_PLUS_ONE: usize>(
+    outer_size: usize,
+    sizes: &[usize; N],
+) -> [usize; N_PLUS_ONE]
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    let mut shape = [outer_size; N_PLUS_ONE];
+    shape[1..].copy_from_slice(sizes);
+    shape
+}
+
+fn coords_of<const N: usize, const N_PLUS_ONE: usize>(
+    offsets: &[usize; N],
+    index: usize,
+) -> [usize; N_PLUS_ONE]
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    let (outer, coords) = split_coords_of(offsets, index);
+    shape_of(outer, &coords)
+}
+
+/// Like `coords_of`, but returns the outer coordinate separately, so it also works where the rank
+/// is only known as `N`.
+fn split_coords_of<const N: usize>(offsets: &[usize; N], mut index: usize) -> (usize, [usize; N]) {
+    let Some((stride, offsets)) = offsets.split_first() else {
+        return (index, [0; N]);
+    };
+    let outer = index / stride;
+    index %= stride;
+    let mut coords = [0; N];
+    for (coord, offset) in coords.iter_mut().zip(offsets.iter().chain([&1])) {
+        *coord = index / offset;
+        index %= offset;
+    }
+    (outer, coords)
+}
+
+fn flat_of<const N: usize, const N_PLUS_ONE: usize>(
+    offsets: &[usize; N],
+    coords: &[usize; N_PLUS_ONE],
+) -> usize
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    coords
+        .iter()
+        .zip(offsets.iter().chain([&1]))
+        .map(|(coord, offset)| coord * offset)
+        .sum()
+}
+
+fn checked_flat_of<const N: usize, const N_PLUS_ONE: usize>(
+    offsets: &[usize; N],
+    shape: [usize; N_PLUS_ONE],
+    coords: &[usize; N_PLUS_ONE],
+) -> usize
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    for (axis, (coord, extent)) in coords.iter().zip(shape).enumerate() {
+        assert!(
+            *coord < extent,
+            "index {coord} is out of range for dimension {axis} of extent {extent}"
+        );
+    }
+    flat_of(offsets, coords)
+}
+
+/// What [`MultiVecRef::get_bordered`] returns for coordinates that fall outside the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Border<T> {
+    /// `T::default()`, which is zero for the numeric types. Since this needs `T: Default`, only
+    /// [`get_bordered_or_default`](MultiVecBase::get_bordered_or_default) accepts it.
+    Zero,
+    /// The nearest element on the edge.
+    Clamp,
+    /// The element on the opposite side, wrapping each axis by its extent.
+    Wrap,
+    /// A fixed value.
+    Value(T),
+}
+
+/// A grid with `N + 1` dimensions whose elements are stored in row-major order in `D`.
+///
+/// The storage decides what the grid can do: [`MultiVec`] owns its elements, [`MultiVecRef`]
+/// is a shared view that only allows reading, and [`MultiVecMut`] is a unique view that also
+/// allows mutating the elements in place. Methods that only read are available on all of
+/// them.
+pub struct MultiVecBase<D, const N: usize, T> {
+    inner: D,
+    outer: usize,
+    sizes: [usize; N],
+    offsets: [usize; N],
+    refs: Refs,
+    _elem: PhantomData<fn() -> T>,
+}
+
+/// An owned grid.
+pub type MultiVec<const N: usize, T> = MultiVecBase<Vec<T>, N, T>;
+
+/// A shared view of a grid, as returned by [`view`](MultiVecBase::view) and
+/// [`row`](MultiVecBase::row).
+///
+/// Like `&[T]`, a shared view can only be sent to another thread if `T` is `Sync`, since both
+/// threads could then reach the same elements:
+///
+/// ```compile_fail
+/// let m = multi_vec::MultiVec::<0, core::cell::Cell<u8>>::filled_default(2, []);
+/// let view = m.view();
+/// std::thread::scope(|scope| {
+///     scope.spawn(move || view[0].set(1));
+/// });
+/// ```
+pub type MultiVecRef<'a, const N: usize, T> = MultiVecBase<&'a [T], N, T>;
+
+/// A unique view of a grid, as returned by [`view_mut`](MultiVecBase::view_mut) and
+/// [`row_mut`](MultiVecBase::row_mut).
+///
+/// Like `&mut [T]`, a unique view can be sent to another thread whenever `T` is `Send`.
+pub type MultiVecMut<'a, const N: usize, T> = MultiVecBase<&'a mut [T], N, T>;
+
+impl<D, const N: usize, T> MultiVecBase<D, N, T> {
+    /// The outer size and inner extents are stored rather than derived from the length of
+    /// `inner`, since a zero extent would otherwise hide the extents before it. `inner` must hold
+    /// exactly `outer * sizes.iter().product()` elements.
+    fn from_parts(inner: D, outer: usize, sizes: [usize; N]) -> Self {
+        Self {
+            inner,
+            outer,
+            sizes,
+            offsets: offsets_of(sizes).unwrap_or_else(|e| panic!("{e}")),
+            refs: Refs::new(),
+            _elem: PhantomData,
+        }
+    }
+
+    fn offsets(&self) -> &[usize; N] {
+        &self.offsets
+    }
+
+    fn stride(&self) -> usize {
+        self.offsets.first().cloned().unwrap_or(1)
+    }
+
+    /// Elements are stored in row-major order and `strides()[d]` is the product of the extents
+    /// of all dimensions after `d`. The element at `[i_0, ..., i_N]` therefore lives at flat
+    /// index `i_0 * strides()[0] + ... + i_{N-1} * strides()[N - 1] + i_N`.
+    pub fn strides(&self) -> [usize; N] {
+        self.offsets
+    }
+
+    pub fn size(&self) -> usize {
+        self.outer
+    }
+
+    pub fn shape<const N_PLUS_ONE: usize>(&self) -> [usize; N_PLUS_ONE]
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        shape_of(self.outer, &self.sizes)
+    }
+
+    fn shape_vec(&self) -> Vec<usize> {
+        [self.outer].into_iter().chain(self.sizes).collect()
+    }
+
+    /// The extents of every dimension except the outer one.
+    pub fn inner_shape(&self) -> [usize; N] {
+        self.sizes
+    }
+}
+
+impl<D: Data<T>, const N: usize, T> MultiVecBase<D, N, T> {
+    fn slice(&self) -> &[T] {
+        self.inner.slice()
+    }
+
+    fn check_row(&self, index: usize) {
+        let size = self.size();
+        assert!(
+            index < size,
+            "index {index} is out of range for size {size}"
+        );
+    }
+
+    pub fn to_owned(&self) -> MultiVec<N, T>
+    where
+        T: Clone,
+    {
+        MultiVec::from_parts(self.slice().to_vec(), self.outer, self.sizes)
+    }
+
+    /// Borrows the grid as a shared view.
+    pub fn view(&self) -> MultiVecRef<'_, N, T> {
+        MultiVecBase::from_parts(self.slice(), self.outer, self.sizes)
+    }
+
+    /// Borrows the outer row at `index` as a shared view. Panics if `index` is out of range.
+    pub fn row<const N_MINUS_ONE: usize>(&self, index: usize) -> MultiVecRef<'_, N_MINUS_ONE, T>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        self.check_row(index);
+        let stride = self.stride();
+        let (size, sizes) = self.sizes.arr_split_first();
+        MultiVecBase::from_parts(
+            &self.slice()[index * stride..(index + 1) * stride],
+            *size,
+            *sizes,
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slice().is_empty()
+    }
+
+    pub fn element_count(&self) -> usize {
+        self.slice().len()
+    }
+
+    pub fn as_flat_slice(&self) -> &[T] {
+        self.slice()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.slice().iter()
+    }
+
+    pub fn view_sub<const N_PLUS_ONE: usize>(
+        &self,
+        starts: [usize; N_PLUS_ONE],
+        lens: [usize; N_PLUS_ONE],
+    ) -> MultiVec<N, T>
+    where
+        T: Clone,
+        [usize; N_PLUS_ONE]: NonEmptyArray<N, Item = usize>,
+    {
+        let shape: [usize; N_PLUS_ONE] = self.shape();
+        for axis in 0..N_PLUS_ONE {
+            assert!(
+                starts[axis]
+                    .checked_add(lens[axis])
+                    .is_some_and(|end| end <= shape[axis]),
+                "region of length {} at {} is out of range for dimension {axis} of extent {}",
+                lens[axis],
+                starts[axis],
+                shape[axis]
+            );
+        }
+        MultiVec::from_fn_coords(lens, |mut coords| {
+            for (coord, start) in coords.iter_mut().zip(starts) {
+                *coord += start;
+            }
+            self.slice()[flat_of(self.offsets(), &coords)].clone()
+        })
+    }
+
+    /// Compares the shapes of two views without looking at their elements.
+    pub fn same_shape<E, U>(&self, other: &MultiVecBase<E, N, U>) -> bool {
+        self.outer == other.outer && self.sizes == other.sizes
+    }
+
+    /// Like [`same_shape`](Self::same_shape), but ignores the outer dimension.
+    pub fn inner_shape_eq<E, U>(&self, other: &MultiVecBase<E, N, U>) -> bool {
+        self.sizes == other.sizes
+    }
+
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = MultiVecRef<'_, N, T>> + '_ {
+        assert!(size != 0, "window size must be non-zero");
+        let (slice, stride, sizes) = (self.slice(), self.stride(), self.sizes);
+        (0..(self.outer + 1).saturating_sub(size)).map(move |i| {
+            MultiVecBase::from_parts(&slice[i * stride..(i + size) * stride], size, sizes)
+        })
+    }
+
+    pub fn chunks_outer(&self, chunk: usize) -> impl Iterator<Item = MultiVecRef<'_, N, T>> + '_ {
+        assert!(chunk != 0, "chunk size must be non-zero");
+        let (slice, stride, sizes, outer) = (self.slice(), self.stride(), self.sizes, self.outer);
+        (0..outer.div_ceil(chunk)).map(move |c| {
+            let (start, rows) = (c * chunk, chunk.min(outer - c * chunk));
+            MultiVecBase::from_parts(&slice[start * stride..(start + rows) * stride], rows, sizes)
+        })
+    }
+
+    /// Yields the elements whose coordinate along `axis` is `index`, in row-major order.
+    pub fn iter_axis(&self, axis: usize, index: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(axis <= N, "axis {axis} is out of range for rank {}", N + 1);
+        let offsets = self.offsets();
+        let block = match axis {
+            0 => self.slice().len(),
+            _ => offsets[axis - 1],
+        };
+        let step = offsets.get(axis).cloned().unwrap_or(1);
+        let extent = axis.checked_sub(1).map_or(self.outer, |k| self.sizes[k]);
+        assert!(
+            index < extent,
+            "index {index} is out of range for dimension {axis} of extent {extent}"
+        );
+        self.slice()
+            .chunks(block.max(1))
+            .flat_map(move |chunk| &chunk[index * step..(index + 1) * step])
+    }
+
+    pub fn indexed_iter<const N_PLUS_ONE: usize>(
+        &self,
+    ) -> impl Iterator<Item = ([usize; N_PLUS_ONE], &T)> + '_
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let offsets = self.offsets();
+        self.slice()
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (coords_of(offsets, i), v))
+    }
+
+    /// # Safety
+    ///
+    /// Every coordinate must be within the extent of its dimension, i.e. less than the
+    /// corresponding entry of [`shape`](Self::shape).
+    pub unsafe fn get_unchecked<const N_PLUS_ONE: usize>(&self, coords: [usize; N_PLUS_ONE]) -> &T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let index = flat_of(self.offsets(), &coords);
+        unsafe { self.slice().get_unchecked(index) }
+    }
+
+    pub fn get_flat(&self, i: usize) -> Option<&T> {
+        self.slice().get(i)
+    }
+
+    pub fn flat_index<const N_PLUS_ONE: usize>(&self, coords: [usize; N_PLUS_ONE]) -> usize
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        checked_flat_of(self.offsets(), self.shape(), &coords)
+    }
+
+    pub fn coords_from_flat<const N_PLUS_ONE: usize>(&self, i: usize) -> [usize; N_PLUS_ONE]
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let len = self.slice().len();
+        assert!(i < len, "index {i} is out of range for {len} elements");
+        coords_of(self.offsets(), i)
+    }
+
+    fn get_resolved<const N_PLUS_ONE: usize>(
+        &self,
+        coords: [isize; N_PLUS_ONE],
+        resolve: impl Fn(isize, usize) -> usize,
+    ) -> &T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        assert!(!self.is_empty(), "cannot index into an empty grid");
+        let shape: [usize; N_PLUS_ONE] = self.shape();
+        let mut resolved = [0; N_PLUS_ONE];
+        for ((r, coord), extent) in resolved.iter_mut().zip(coords).zip(shape) {
+            *r = resolve(coord, extent);
+        }
+        &self.slice()[flat_of(self.offsets(), &resolved)]
+    }
+
+    pub fn get_wrapping<const N_PLUS_ONE: usize>(&self, coords: [isize; N_PLUS_ONE]) -> &T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        self.get_resolved(coords, |coord, extent| {
+            coord.rem_euclid(extent as isize) as usize
+        })
+    }
+
+    pub fn get_clamped<const N_PLUS_ONE: usize>(&self, coords: [isize; N_PLUS_ONE]) -> &T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        self.get_resolved(coords, |coord, extent| {
+            coord.clamp(0, extent as isize - 1) as usize
+        })
+    }
+
+    /// Panics on [`Border::Zero`], which needs `T: Default`; use
+    /// [`get_bordered_or_default`](Self::get_bordered_or_default) for that.
+    pub fn get_bordered<const N_PLUS_ONE: usize>(
+        &self,
+        coords: [isize; N_PLUS_ONE],
+        border: &Border<T>,
+    ) -> T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+        T: Clone,
+    {
+        match border {
+            Border::Zero => panic!("Border::Zero needs T: Default, use get_bordered_or_default"),
+            Border::Clamp => self.get_clamped(coords).clone(),
+            Border::Wrap => self.get_wrapping(coords).clone(),
+            Border::Value(value) => self.get_checked(coords).unwrap_or(value).clone(),
+        }
+    }
+
+    /// Like [`get_bordered`](Self::get_bordered), but also accepts [`Border::Zero`].
+    pub fn get_bordered_or_default<const N_PLUS_ONE: usize>(
+        &self,
+        coords: [isize; N_PLUS_ONE],
+        border: &Border<T>,
+    ) -> T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+        T: Clone + Default,
+    {
+        match border {
+            Border::Zero => self.get_checked(coords).cloned().unwrap_or_default(),
+            _ => self.get_bordered(coords, border),
+        }
+    }
+
+    fn get_checked<const N_PLUS_ONE: usize>(&self, coords: [isize; N_PLUS_ONE]) -> Option<&T>
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let shape: [usize; N_PLUS_ONE] = self.shape();
+        let mut checked = [0; N_PLUS_ONE];
+        for ((c, coord), extent) in checked.iter_mut().zip(coords).zip(shape) {
+            *c = usize::try_from(coord).ok().filter(|&c| c < extent)?;
+        }
+        Some(&self.slice()[flat_of(self.offsets(), &checked)])
+    }
+
+    /// Yields every outer row as a shared view, in order.
+    pub fn rows<const N_MINUS_ONE: usize>(
+        &self,
+    ) -> impl Iterator<Item = MultiVecRef<'_, N_MINUS_ONE, T>> + '_
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let (slice, stride) = (self.slice(), self.stride());
+        let (size, sizes) = self.sizes.arr_split_first();
+        (0..self.outer).map(move |i| {
+            MultiVecBase::from_parts(&slice[i * stride..(i + 1) * stride], *size, *sizes)
+        })
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.slice().contains(value)
+    }
+
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.slice().iter().filter(|v| *v == value).count()
+    }
+
+    pub fn all(&self, pred: impl Fn(&T) -> bool) -> bool {
+        self.slice().iter().all(pred)
+    }
+
+    pub fn any(&self, pred: impl Fn(&T) -> bool) -> bool {
+        self.slice().iter().any(pred)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn counts(&self) -> HashMap<T, usize>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut counts = HashMap::new();
+        for v in self.slice() {
+            *counts.entry(v.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn position<const N_PLUS_ONE: usize>(&self, value: &T) -> Option<[usize; N_PLUS_ONE]>
+    where
+        T: PartialEq,
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let index = self.slice().iter().position(|v| v == value)?;
+        Some(coords_of(self.offsets(), index))
+    }
+
+    pub fn find_map<const N_PLUS_ONE: usize, R>(
+        &self,
+        mut f: impl FnMut([usize; N_PLUS_ONE], &T) -> Option<R>,
+    ) -> Option<R>
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        self.indexed_iter().find_map(|(coords, v)| f(coords, v))
+    }
+
+    /// Returns the coordinate of the largest element, preferring the first one in row-major
+    /// order if several are equal.
+    pub fn argmax<const N_PLUS_ONE: usize>(&self) -> Option<[usize; N_PLUS_ONE]>
+    where
+        T: Ord,
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let (index, _) = self
+            .slice()
+            .iter()
+            .enumerate()
+            .max_by(|(i, a), (j, b)| a.cmp(b).then(j.cmp(i)))?;
+        Some(coords_of(self.offsets(), index))
+    }
+
+    /// Returns the coordinate of the smallest element, preferring the first one in row-major
+    /// order if several are equal.
+    pub fn argmin<const N_PLUS_ONE: usize>(&self) -> Option<[usize; N_PLUS_ONE]>
+    where
+        T: Ord,
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let (index, _) = self
+            .slice()
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))?;
+        Some(coords_of(self.offsets(), index))
+    }
+
+    pub fn max_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.slice().iter().max()
+    }
+
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.slice().iter().min()
+    }
+
+    pub fn fold<B>(&self, init: B, f: impl FnMut(B, &T) -> B) -> B {
+        self.slice().iter().fold(init, f)
+    }
+
+    pub fn reduce(&self, mut f: impl FnMut(&T, &T) -> T) -> Option<T>
+    where
+        T: Clone,
+    {
+        let (first, rest) = self.slice().split_first()?;
+        Some(rest.iter().fold(first.clone(), |acc, v| f(&acc, v)))
+    }
+
+    pub fn sum<S>(&self) -> S
+    where
+        S: for<'a> Sum<&'a T>,
+    {
+        self.slice().iter().sum()
+    }
+
+    pub fn product<P>(&self) -> P
+    where
+        P: for<'a> Product<&'a T>,
+    {
+        self.slice().iter().product()
+    }
+}
+
+impl<D: DataMut<T>, const N: usize, T> MultiVecBase<D, N, T> {
+    fn slice_mut(&mut self) -> &mut [T] {
+        self.inner.slice_mut()
+    }
+
+    /// Borrows the grid as a unique view.
+    pub fn view_mut(&mut self) -> MultiVecMut<'_, N, T> {
+        let (outer, sizes) = (self.outer, self.sizes);
+        MultiVecBase::from_parts(self.slice_mut(), outer, sizes)
+    }
+
+    /// Borrows the outer row at `index` as a unique view. Panics if `index` is out of range.
+    pub fn row_mut<const N_MINUS_ONE: usize>(
+        &mut self,
+        index: usize,
+    ) -> MultiVecMut<'_, N_MINUS_ONE, T>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        self.check_row(index);
+        let stride = self.stride();
+        let (size, sizes) = self.sizes.arr_split_first();
+        let (size, sizes) = (*size, *sizes);
+        MultiVecBase::from_parts(
+            &mut self.slice_mut()[index * stride..(index + 1) * stride],
+            size,
+            sizes,
+        )
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.slice_mut().iter_mut()
+    }
+
+    pub fn indexed_iter_mut<const N_PLUS_ONE: usize>(
+        &mut self,
+    ) -> impl Iterator<Item = ([usize; N_PLUS_ONE], &mut T)> + '_
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let offsets = *self.offsets();
+        self.slice_mut()
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, v)| (coords_of(&offsets, i), v))
+    }
+
+    /// # Safety
+    ///
+    /// Every coordinate must be within the extent of its dimension, i.e. less than the
+    /// corresponding entry of [`shape`](Self::shape).
+    pub unsafe fn get_unchecked_mut<const N_PLUS_ONE: usize>(
+        &mut self,
+        coords: [usize; N_PLUS_ONE],
+    ) -> &mut T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let index = flat_of(self.offsets(), &coords);
+        unsafe { self.slice_mut().get_unchecked_mut(index) }
+    }
+
+    pub fn set_flat(&mut self, i: usize, value: T) {
+        self.slice_mut()[i] = value;
+    }
+
+    /// Yields every outer row as a mutable view. The views cover disjoint parts of the buffer,
+    /// so they can be held and mutated at the same time.
+    pub fn rows_mut<const N_MINUS_ONE: usize>(
+        &mut self,
+    ) -> impl Iterator<Item = MultiVecMut<'_, N_MINUS_ONE, T>> + '_
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let stride = self.stride();
+        let (size, sizes) = self.sizes.arr_split_first();
+        let (size, sizes, outer) = (*size, *sizes, self.outer);
+        let mut rest = self.slice_mut();
+        (0..outer).map(move |_| {
+            let (row, tail) = core::mem::take(&mut rest).split_at_mut(stride);
+            rest = tail;
+            MultiVecBase::from_parts(row, size, sizes)
+        })
+    }
+
+    /// Splits the view into the outer rows before and after `index`. The two views cover
+    /// disjoint parts of the buffer, so they can be mutated at the same time.
+    pub fn split_at_mut(&mut self, index: usize) -> (MultiVecMut<'_, N, T>, MultiVecMut<'_, N, T>) {
+        let size = self.size();
+        assert!(
+            index <= size,
+            "index {index} is out of range for size {size}"
+        );
+        let (sizes, stride) = (self.sizes, self.stride());
+        let (left, right) = self.slice_mut().split_at_mut(index * stride);
+        (
+            MultiVecBase::from_parts(left, index, sizes),
+            MultiVecBase::from_parts(right, size - index, sizes),
+        )
+    }
+}
+
+impl<D: Clone, const N: usize, T> Clone for MultiVecBase<D, N, T> {
+    fn clone(&self) -> Self {
+        Self::from_parts(self.inner.clone(), self.outer, self.sizes)
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+        self.outer = source.outer;
+        self.sizes = source.sizes;
+        self.offsets = source.offsets;
+    }
+}
+
+impl<D: Data<T>, const N: usize, T: Debug> MultiVecBase<D, N, T> {
+    fn fmt_named(&self, f: &mut core::fmt::Formatter<'_>, name: &str) -> core::fmt::Result {
+        let shape = self.shape_vec();
+        let slice = DebugSlice::new(f, self.slice(), &shape, &self.offsets);
+        f.debug_tuple(&format!("{name}<{N}>"))
+            .field(&slice)
+            .finish()
+    }
+}
+
+impl<const N: usize, T: Debug> Debug for MultiVecRef<'_, N, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_named(f, "MultiVecRef")
+    }
+}
+
+impl<const N: usize, T: Debug> Debug for MultiVecMut<'_, N, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_named(f, "MultiVecMut")
+    }
+}
+
+impl<const N: usize, T: PartialEq> PartialEq for MultiVec<N, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.outer == other.outer && self.sizes == other.sizes && self.inner == other.inner
+    }
+}
+
+impl<const N: usize, T: Eq> Eq for MultiVec<N, T> {}
+
+/// Grids are ordered by their strides first, so the inner shape is the primary sort key. Grids
+/// with the same strides are then ordered lexicographically by their elements in row-major order,
+/// which also orders a grid after its own leading rows. Empty grids that differ only in their
+/// shape are ordered by their shape.
+impl<const N: usize, T: PartialOrd> PartialOrd for MultiVec<N, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.offsets.cmp(&other.offsets) {
+            Ordering::Equal => match self.inner.partial_cmp(&other.inner)? {
+                Ordering::Equal => Some((self.outer, self.sizes).cmp(&(other.outer, other.sizes))),
+                ord => Some(ord),
+            },
+            ord => Some(ord),
+        }
+    }
+}
+
+impl<const N: usize, T: Ord> Ord for MultiVec<N, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.offsets
+            .cmp(&other.offsets)
+            .then_with(|| self.inner.cmp(&other.inner))
+            .then_with(|| (self.outer, self.sizes).cmp(&(other.outer, other.sizes)))
+    }
+}
+
+impl<const N: usize, T> Default for MultiVec<N, T> {
+    fn default() -> Self {
+        Self::from_parts(Vec::new(), 0, [0; N])
+    }
+}
+
+impl<const N: usize, T: Debug> Debug for MultiVec<N, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_named(f, "MultiVec")
+    }
+}
+
+impl<const N: usize, T> MultiVec<N, T> {
+    pub fn filled_default(outer_size: usize, sizes: [usize; N]) -> Self
+    where
+        T: Default + Clone,
+    {
+        Self::from_fn(outer_size, sizes, |_, _| Default::default())
+    }
+
+    /// Builds a grid by calling `f` with the outer index and inner coordinates of every cell.
+    /// Cells are generated in row-major order, so any side effects of `f` happen in that order.
+    pub fn from_fn(
+        outer_size: usize,
+        sizes: [usize; N],
+        mut f: impl FnMut(usize, [usize; N]) -> T,
+    ) -> Self {
+        Self::try_from_fn(outer_size, sizes, |i, coords| {
+            Ok::<_, ShapeError>(f(i, coords))
+        })
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn from_fn_coords<const N_PLUS_ONE: usize>(
+        sizes: [usize; N_PLUS_ONE],
+        mut f: impl FnMut([usize; N_PLUS_ONE]) -> T,
+    ) -> Self
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N, Item = usize>,
+    {
+        let (outer_size, sizes) = sizes.arr_split_first();
+        Self::from_fn(*outer_size, *sizes, |i, rest| {
+            let mut coords = [i; N_PLUS_ONE];
+            coords[1..].copy_from_slice(&rest);
+            f(coords)
+        })
+    }
+
+    /// Like `from_fn`, but stops at the first error returned by `f`. Cells are visited in the
+    /// same order as `from_fn`.
+    pub fn try_from_fn<E>(
+        outer_size: usize,
+        sizes: [usize; N],
+        mut f: impl FnMut(usize, [usize; N]) -> Result<T, E>,
+    ) -> Result<Self, E>
+    where
+        E: From<ShapeError>,
+    {
+        let offsets = offsets_of(sizes)?;
+        len_of(outer_size, &offsets)?;
+
+        let inner = [outer_size]
+            .into_iter()
+            .chain(sizes)
+            .map(|n| 0..n)
+            .multi_cartesian_product()
+            .map(|mut indices| {
+                let first = indices.remove(0);
+                f(first, indices.try_into().unwrap())
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_parts(inner, outer_size, sizes))
+    }
+
+    fn mismatch<U>(&self, other: &MultiVec<N, U>) -> ShapeError {
+        ShapeError::Mismatch {
+            left: self.shape_vec(),
+            right: other.shape_vec(),
+        }
+    }
+
+    pub fn as_flat_vec(&self) -> &Vec<T> {
+        &self.inner
+    }
+
+    pub fn into_flat_vec(self) -> Vec<T> {
+        self.inner
+    }
+
+    /// Appends an outer row. A row that is too long is not consumed past its first extra element,
+    /// so `actual` in the error is then only a lower bound.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) -> Result<(), ShapeError> {
+        let expected = self.stride();
+        let start = self.inner.len();
+        let mut row = row.into_iter();
+        self.inner.extend(row.by_ref().take(expected));
+        let mut actual = self.inner.len() - start;
+        if actual == expected && row.next().is_some() {
+            actual += 1;
+        }
+        if actual != expected {
+            self.inner.truncate(start);
+            return Err(ShapeError::RowLength { expected, actual });
+        }
+        self.outer += 1;
+        Ok(())
+    }
+
+    /// Inserts an outer row before `at`, with the same length checks as
+    /// [`push_row`](Self::push_row).
+    pub fn insert_row(
+        &mut self,
+        at: usize,
+        row: impl IntoIterator<Item = T>,
+    ) -> Result<(), ShapeError> {
+        let size = self.size();
+        assert!(at <= size, "index {at} is out of range for size {size}");
+        let expected = self.stride();
+        let mut row = row.into_iter();
+        let items: Vec<T> = row.by_ref().take(expected).collect();
+        let mut actual = items.len();
+        if actual == expected && row.next().is_some() {
+            actual += 1;
+        }
+        if actual != expected {
+            return Err(ShapeError::RowLength { expected, actual });
+        }
+        self.inner.splice(at * expected..at * expected, items);
+        self.outer += 1;
+        Ok(())
+    }
+
+    pub fn remove_row<const N_MINUS_ONE: usize>(&mut self, at: usize) -> MultiVec<N_MINUS_ONE, T>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let size = self.size();
+        assert!(at < size, "index {at} is out of range for size {size}");
+        let stride = self.stride();
+        let (row_size, sizes) = self.sizes.arr_split_first();
+        self.outer -= 1;
+        MultiVec::from_parts(
+            self.inner.drain(at * stride..(at + 1) * stride).collect(),
+            *row_size,
+            *sizes,
+        )
+    }
+
+    /// Removes the outer row at `at` and moves the last row into its place, so only one row is
+    /// moved instead of every row after it.
+    pub fn swap_remove_row<const N_MINUS_ONE: usize>(
+        &mut self,
+        at: usize,
+    ) -> MultiVec<N_MINUS_ONE, T>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let size = self.size();
+        assert!(at < size, "index {at} is out of range for size {size}");
+        let stride = self.stride();
+        let (row_size, sizes) = self.sizes.arr_split_first();
+        let last = (size - 1) * stride;
+        if at != size - 1 {
+            let (front, back) = self.inner.split_at_mut(last);
+            front[at * stride..(at + 1) * stride].swap_with_slice(back);
+        }
+        self.outer -= 1;
+        MultiVec::from_parts(self.inner.drain(last..).collect(), *row_size, *sizes)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
+    pub fn concat_outer(mut self, mut other: Self) -> Result<Self, ShapeError> {
+        if self.sizes != other.sizes {
+            return Err(self.mismatch(&other));
+        }
+        self.inner.append(&mut other.inner);
+        self.outer += other.outer;
+        Ok(self)
+    }
+
+    pub fn extend_from_grid(&mut self, other: &Self) -> Result<(), ShapeError>
+    where
+        T: Clone,
+    {
+        if self.sizes != other.sizes {
+            return Err(self.mismatch(other));
+        }
+        self.inner.extend_from_slice(&other.inner);
+        self.outer += other.outer;
+        Ok(())
+    }
+
+    pub fn copy_from_flat(&mut self, src: &[T]) -> Result<(), ShapeError>
+    where
+        T: Copy,
+    {
+        if src.len() != self.inner.len() {
+            return Err(ShapeError::ElementCount {
+                expected: self.inner.len(),
+                actual: src.len(),
+            });
+        }
+        self.inner.copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the element at `coords` without going through a row view.
+    pub fn entry<const N_PLUS_ONE: usize>(&mut self, coords: [usize; N_PLUS_ONE]) -> &mut T
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let index = checked_flat_of(&self.offsets, self.shape(), &coords);
+        &mut self.inner[index]
+    }
+
+    /// Returns mutable references to two distinct elements at once, or `None` if the
+    /// coordinates are equal or either is out of range.
+    pub fn get_two_mut<const N_PLUS_ONE: usize>(
+        &mut self,
+        a: [usize; N_PLUS_ONE],
+        b: [usize; N_PLUS_ONE],
+    ) -> Option<(&mut T, &mut T)>
+    where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let shape: [usize; N_PLUS_ONE] = self.shape();
+        let in_range = |coords: &[usize; N_PLUS_ONE]| coords.iter().zip(shape).all(|(c, e)| *c < e);
+        if !in_range(&a) || !in_range(&b) {
+            return None;
+        }
+        let [a, b] = self
+            .inner
+            .get_disjoint_mut([flat_of(&self.offsets, &a), flat_of(&self.offsets, &b)])
+            .ok()?;
+        Some((a, b))
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.outer = 0;
+    }
+
+    pub fn truncate_outer(&mut self, len: usize) {
+        self.outer = self.outer.min(len);
+        self.inner.truncate(self.outer * self.stride());
+    }
+
+    pub fn drain_outer<const N_MINUS_ONE: usize>(
+        &mut self,
+    ) -> impl Iterator<Item = MultiVec<N_MINUS_ONE, T>> + '_
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let (size, stride) = (core::mem::take(&mut self.outer), self.stride());
+        let (row_size, sizes) = self.sizes.arr_split_first();
+        let (row_size, sizes) = (*row_size, *sizes);
+        let mut inner = core::mem::take(&mut self.inner).into_iter();
+        (0..size).map(move |_| {
+            MultiVec::from_parts(inner.by_ref().take(stride).collect(), row_size, sizes)
+        })
+    }
+
+    /// Splits the outer rows into `parts` grids whose sizes differ by at most one, with the
+    /// larger ones first. Panics if `parts` is zero.
+    pub fn split_outer_into(self, parts: usize) -> Vec<Self> {
+        assert!(parts != 0, "number of parts must be non-zero");
+        let size = self.size();
+        let stride = self.stride();
+        let (base, extra) = (size / parts, size % parts);
+        let mut inner = self.inner.into_iter();
+        (0..parts)
+            .map(|i| {
+                let rows = base + usize::from(i < extra);
+                MultiVec::from_parts(
+                    inner.by_ref().take(rows * stride).collect(),
+                    rows,
+                    self.sizes,
+                )
+            })
+            .collect()
+    }
+
+    pub fn retain_outer<const N_MINUS_ONE: usize>(
+        &mut self,
+        mut keep: impl FnMut(MultiVecRef<'_, N_MINUS_ONE, T>) -> bool,
+    ) where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let stride = self.stride();
+        let mut kept = 0;
+        for i in 0..self.size() {
+            if keep(self.row(i)) {
+                if kept != i {
+                    let (front, back) = self.inner.split_at_mut(i * stride);
+                    front[kept * stride..(kept + 1) * stride].swap_with_slice(&mut back[..stride]);
+                }
+                kept += 1;
+            }
+        }
+        self.inner.truncate(kept * stride);
+        self.outer = kept;
+    }
+
+    pub fn resize_outer(&mut self, new_outer: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.inner.resize(new_outer * self.stride(), value);
+        self.outer = new_outer;
+    }
+
+    pub fn map_inplace(&mut self, f: impl FnMut(&mut T)) {
+        self.inner.iter_mut().for_each(f);
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> MultiVec<N, U> {
+        MultiVec::from_parts(
+            self.inner.into_iter().map(f).collect(),
+            self.outer,
+            self.sizes,
+        )
+    }
+
+    pub fn map_into<U: From<T>>(self) -> MultiVec<N, U> {
+        self.map(Into::into)
+    }
+
+    pub fn for_each_mut<const N_PLUS_ONE: usize>(
+        &mut self,
+        mut f: impl FnMut([usize; N_PLUS_ONE], &mut T),
+    ) where
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        for (i, v) in self.inner.iter_mut().enumerate() {
+            f(coords_of(&self.offsets, i), v);
+        }
+    }
+}
+
+impl<D: Data<T>, T> Index<usize> for MultiVecBase<D, 0, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.slice()[index]
+    }
+}
+
+impl<D: DataMut<T>, T> IndexMut<usize> for MultiVecBase<D, 0, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.slice_mut()[index]
+    }
+}
+
+impl<D: Data<T>, const N: usize, const N_PLUS_ONE: usize, T> Index<[usize; N_PLUS_ONE]>
+    for MultiVecBase<D, N, T>
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    type Output = T;
+
+    fn index(&self, coords: [usize; N_PLUS_ONE]) -> &Self::Output {
+        let index = checked_flat_of(self.offsets(), self.shape(), &coords);
+        &self.slice()[index]
+    }
+}
+
+impl<D: DataMut<T>, const N: usize, const N_PLUS_ONE: usize, T> IndexMut<[usize; N_PLUS_ONE]>
+    for MultiVecBase<D, N, T>
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    fn index_mut(&mut self, coords: [usize; N_PLUS_ONE]) -> &mut Self::Output {
+        let index = checked_flat_of(self.offsets(), self.shape(), &coords);
+        &mut self.slice_mut()[index]
+    }
+}
+
+impl<'a, T> MultiVecRef<'a, 0, T> {
+    pub fn try_get(self, index: usize) -> Option<&'a T> {
+        self.inner.get(index)
+    }
+}
+
+impl<T> MultiVec<0, T> {
+    pub fn try_get(&self, index: usize) -> Option<&T> {
+        self.inner.get(index)
+    }
+
+    /// Reinterprets the flat buffer as a `rows` × `cols` grid without copying.
+    pub fn as_2d(self, rows: usize, cols: usize) -> Result<MultiVec<1, T>, ShapeError> {
+        self.unflatten(rows, [cols])
+    }
+
+    /// Reinterprets the flat buffer as a `depth` × `rows` × `cols` grid without copying.
+    pub fn as_3d(
+        self,
+        depth: usize,
+        rows: usize,
+        cols: usize,
+    ) -> Result<MultiVec<2, T>, ShapeError> {
+        self.unflatten(depth, [rows, cols])
+    }
+
+    fn unflatten<const M: usize>(
+        self,
+        outer_size: usize,
+        sizes: [usize; M],
+    ) -> Result<MultiVec<M, T>, ShapeError> {
+        let offsets = offsets_of(sizes)?;
+        let expected = len_of(outer_size, &offsets)?;
+        if expected != self.inner.len() {
+            return Err(ShapeError::ElementCount {
+                expected,
+                actual: self.inner.len(),
+            });
+        }
+        Ok(MultiVec::from_parts(self.inner, outer_size, sizes))
+    }
+}
+
+impl<'a, const N: usize, T> MultiVecRef<'a, N, T>
+where
+    [usize; N]: NonEmpty,
+{
+    /// Returns the row at `index`, or `None` if it is out of range. This consumes the view so
+    /// that calls can be chained with `?`.
+    pub fn try_get<const N_MINUS_ONE: usize>(
+        self,
+        index: usize,
+    ) -> Option<MultiVecRef<'a, N_MINUS_ONE, T>>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        if index >= self.size() {
+            return None;
+        }
+        let stride = self.stride();
+        let (size, sizes) = self.sizes.arr_split_first();
+        let inner = self.inner;
+        Some(MultiVecBase::from_parts(
+            &inner[index * stride..(index + 1) * stride],
+            *size,
+            *sizes,
+        ))
+    }
+}
+
+impl<const N: usize, T> MultiVec<N, T>
+where
+    [usize; N]: NonEmpty,
+{
+    pub fn try_get<const N_MINUS_ONE: usize>(
+        &self,
+        index: usize,
+    ) -> Option<MultiVecRef<'_, N_MINUS_ONE, T>>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        self.view().try_get(index)
+    }
+}
+
+macro_rules! impl_index {
+    ($n:expr) => {
+        impl<D: Data<T>, T> Index<usize> for MultiVecBase<D, $n, T> {
+            type Output = Row<{ $n - 1 }, T>;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                let row = Row::alloc(&self.refs, RowData::shared(self.row(index)));
+                unsafe { &*row }
+            }
+        }
+
+        impl<D: DataMut<T>, T> IndexMut<usize> for MultiVecBase<D, $n, T> {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                self.refs.clear();
+                let data = RowData::unique(self.row_mut(index));
+                unsafe { &mut *Row::alloc(&self.refs, data) }
+            }
+        }
+
+        impl<T> Index<usize> for Row<$n, T> {
+            type Output = Row<{ $n - 1 }, T>;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                let row = Row::alloc(self.refs(), RowData::shared(self.view().row(index)));
+                unsafe { &*row }
+            }
+        }
+
+        impl<T> IndexMut<usize> for Row<$n, T> {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                self.refs_mut().clear();
+                let data = RowData::unique(self.view_mut().row_mut(index));
+                unsafe { &mut *Row::alloc(self.refs(), data) }
+            }
+        }
+    };
+}
+
+impl_index!(1);
+impl_index!(2);
+impl_index!(3);
+impl_index!(4);
+impl_index!(5);
+impl_index!(6);
+impl_index!(7);
+impl_index!(8);
+impl_index!(9);
+impl_index!(10);
+impl_index!(11);
+impl_index!(12);
+impl_index!(13);
+impl_index!(14);
+impl_index!(15);
+impl_index!(16);
+impl_index!(17);
+impl_index!(18);
+impl_index!(19);
+impl_index!(20);
+impl_index!(21);
+impl_index!(22);
+impl_index!(23);
+impl_index!(24);
+impl_index!(25);
+impl_index!(26);
+impl_index!(27);
+impl_index!(28);
+impl_index!(29);
+impl_index!(30);
+impl_index!(31);
+impl_index!(32);
+
+#[cfg(test)]
+mod test {
+    use core::{cell::Cell, cmp::Ordering, num::NonZeroU8};
+
+    use proptest::prelude::*;
+
+    use crate::{Border, MultiVec, MultiVecMut, MultiVecRef, ShapeError};
+
+    const _: () = {
+        const fn assert_send<T: Send>() {}
+        const fn assert_sync<T: Sync>() {}
+        assert_send::<MultiVec<0, u8>>();
+        assert_sync::<MultiVec<0, u8>>();
+        assert_send::<MultiVecRef<'static, 0, u8>>();
+        assert_sync::<MultiVecRef<'static, 0, u8>>();
+        assert_send::<MultiVecMut<'static, 0, Cell<u8>>>();
+    };
+
+    struct NonClone<T>(T);
+
+    #[test]
+    fn test_thread_move() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| NonClone((i, j, k)));
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                drop(m);
+            });
+        });
+    }
+
+    #[test]
+    fn test_thread_ref() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| NonClone((i, j, k)));
+        std::thread::scope(|scope| {
+            scope.spawn(|| &m);
+            scope.spawn(|| &m);
+        });
+    }
+
+    #[test]
+    fn test_chunks_outer() {
+        let m = MultiVec::<1, _>::from_fn(5, [2], |i, [j]| (i, j));
+        let chunks: Vec<_> = m.chunks_outer(2).collect();
+        assert_eq!(
+            chunks.iter().map(|c| c.size()).collect::<Vec<_>>(),
+            [2, 2, 1]
+        );
+        assert_eq!(chunks[1][1][0], (3, 0));
+        assert_eq!(chunks[2][0][1], (4, 1));
+        assert_eq!(chunks[1].as_flat_slice(), &m.as_flat_slice()[4..8]);
+
+        assert_eq!(m.chunks_outer(5).count(), 1);
+        assert_eq!(m.chunks_outer(9).count(), 1);
+        assert_eq!(
+            MultiVec::<1, u8>::filled_default(0, [2])
+                .chunks_outer(2)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn test_chunks_outer_zero() {
+        let m = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        _ = m.chunks_outer(0);
+    }
+
+    #[test]
+    fn test_position() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        assert!(m.contains(&(2, 1, 3)));
+        assert!(!m.contains(&(3, 0, 0)));
+        assert_eq!(m.position(&(2, 1, 3)), Some([2, 1, 3]));
+        assert_eq!(m.row(1).position(&(1, 3, 4)), Some([3, 4]));
+        assert_eq!(m.position(&(0, 4, 0)), None);
+
+        let empty = MultiVec::<1, u8>::filled_default(0, [5]);
+        assert_eq!(empty.position(&0), None);
+    }
+
+    #[test]
+    fn test_find_map() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * j);
+        let mut visited = 0;
+        let found = m.find_map(|[i, j], v| {
+            visited += 1;
+            (*v > 1).then_some(i * 10 + j)
+        });
+        assert_eq!(found, Some(12));
+        assert_eq!(visited, 7);
+        assert_eq!(m.row(2).find_map(|[j], v| (*v == 4).then_some(j)), Some(2));
+        assert_eq!(m.find_map(|_, v| (*v > 6).then_some(())), None);
+
+        let empty = MultiVec::<1, u8>::filled_default(0, [5]);
+        assert_eq!(empty.find_map(|c: [usize; 2], _| Some(c)), None);
+    }
+
+    #[test]
+    fn test_count() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i + j) % 3);
+        assert_eq!(m.count(&0), 4);
+        assert_eq!(m.row(1).count(&0), 1);
+        assert_eq!(m.count(&3), 0);
+    }
+
+    #[test]
+    fn test_all_any() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * j);
+        assert!(m.row(0).all(|v| *v == 0));
+        assert!(!m.all(|v| *v == 0));
+        assert!(m.any(|v| *v == 6));
+        assert!(!m.row(1).any(|v| *v == 6));
+        assert!(m.all(|v| *v < 7));
+
+        let empty = MultiVec::<1, usize>::filled_default(0, [4]);
+        assert!(empty.all(|_| false));
+        assert!(!empty.any(|_| true));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_counts() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i + j) % 3);
+        let counts = m.counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&1], 4);
+        assert_eq!(m.row(2).counts(), [(2, 2), (0, 1), (1, 1)].into());
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let mut m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        unsafe {
+            assert_eq!(*m.get_unchecked([2, 3, 4]), (2, 3, 4));
+            assert_eq!(*m.row(1).get_unchecked([0, 2]), (1, 0, 2));
+            *m.get_unchecked_mut([1, 2, 3]) = (0, 0, 0);
+        }
+        assert_eq!(m[1][2][3], (0, 0, 0));
+    }
+
+    #[test]
+    fn test_flat_access() {
+        let mut m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        assert_eq!(m.get_flat(23), Some(&(1, 2, 3)));
+        assert_eq!(m.get_flat(24), None);
+        assert_eq!(m.row(1).get_flat(5), Some(&(1, 1, 1)));
+
+        m.set_flat(6, (9, 9, 9));
+        assert_eq!(m[0][1][2], (9, 9, 9));
+        m.row_mut(1).set_flat(0, (8, 8, 8));
+        assert_eq!(m.get_flat(12), Some(&(8, 8, 8)));
+
+        for i in 0..m.element_count() {
+            let coords: [usize; 3] = m.coords_from_flat(i);
+            assert_eq!(m.flat_index(coords), i);
+        }
+        assert_eq!(m.coords_from_flat(17), [1, 1, 1]);
+        assert_eq!(m.flat_index([1, 2, 0]), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 24 is out of range for 24 elements")]
+    fn test_coords_from_flat_out_of_range() {
+        let m = MultiVec::<2, u8>::filled_default(2, [3, 4]);
+        let _: [usize; 3] = m.coords_from_flat(24);
+    }
+
+    #[test]
+    fn test_as_2d_3d() {
+        let flat = MultiVec::<0, _>::from_fn(12, [], |i, _| i);
+        let ptr = flat.inner.as_ptr();
+        let m = flat.clone().as_2d(3, 4).unwrap();
+        assert_eq!(m, MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * 4 + j));
+        let m = flat.clone().as_3d(2, 3, 2).unwrap();
+        assert_eq!(m[1][2][1], 11);
+        assert_eq!(m.shape(), [2, 3, 2]);
+
+        let m = flat.as_2d(6, 2).unwrap();
+        assert_eq!(m.inner.as_ptr(), ptr);
+
+        let flat = MultiVec::<0, u8>::filled_default(12, []);
+        assert_eq!(
+            flat.clone().as_2d(5, 2),
+            Err(ShapeError::ElementCount {
+                expected: 10,
+                actual: 12
+            })
+        );
+        assert_eq!(flat.as_3d(2, usize::MAX, 2), Err(ShapeError::Overflow));
+        assert_eq!(
+            MultiVec::<0, u8>::default().as_2d(0, 5).unwrap().shape(),
+            [0, 5]
+        );
+    }
+
+    #[test]
+    fn test_try_get() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let get = |i, j, k| m.try_get(i)?.try_get(j)?.try_get(k);
+        assert_eq!(get(1, 2, 3), Some(&(1, 2, 3)));
+        assert_eq!(get(2, 0, 0), None);
+        assert_eq!(get(0, 3, 0), None);
+        assert_eq!(get(0, 0, 4), None);
+
+        let row = m.try_get(1).unwrap();
+        assert_eq!(row.shape(), [3, 4]);
+        assert!(row.same_shape(&m.try_get(0).unwrap()));
+        assert!(row.try_get(2).unwrap().iter().eq(m.row(1).row(2).iter()));
+        assert_eq!(
+            MultiVec::<0, _>::from_fn(3, [], |i, []| i).try_get(2),
+            Some(&2)
+        );
+        let m = MultiVec::<1, u8>::filled_default(2, [0]);
+        assert_eq!(m.try_get(1).unwrap().size(), 0);
+        assert!(m.try_get(2).is_none());
+
+        let m = MultiVec::<32, u8>::filled_default(1, [1; 32]);
+        let row: MultiVecRef<'_, 31, u8> = m.try_get(0).unwrap();
+        assert_eq!(row.shape(), [1; 32]);
+        assert!(row.try_get(1).is_none());
+    }
+
+    #[test]
+    fn test_get_wrapping_clamped() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i, j));
+        assert_eq!(*m.get_wrapping([1, 2]), (1, 2));
+        assert_eq!(*m.get_wrapping([-1, 4]), (2, 0));
+        assert_eq!(*m.get_wrapping([-7, -9]), (2, 3));
+        assert_eq!(*m.get_clamped([1, 2]), (1, 2));
+        assert_eq!(*m.get_clamped([-1, 4]), (0, 3));
+        assert_eq!(*m.get_clamped([isize::MIN, isize::MAX]), (0, 3));
+        assert_eq!(*m.row(2).get_wrapping([-1]), (2, 3));
+    }
+
+    #[test]
+    fn test_get_bordered() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i * 4 + j) as i32);
+        assert_eq!(m.get_bordered_or_default([1, 2], &Border::Zero), 6);
+        assert_eq!(m.get_bordered_or_default([-1, 2], &Border::Zero), 0);
+        assert_eq!(m.get_bordered_or_default([-1, 4], &Border::Clamp), 3);
+        assert_eq!(m.get_bordered([1, 4], &Border::Value(-1)), -1);
+        assert_eq!(m.get_bordered([2, 3], &Border::Value(-1)), 11);
+        assert_eq!(m.get_bordered([-1, 4], &Border::Clamp), 3);
+        assert_eq!(m.get_bordered([-1, 4], &Border::Wrap), 8);
+        assert_eq!(m.row(1).get_bordered([-1], &Border::Wrap), 7);
+
+        let empty = MultiVec::<1, i32>::filled_default(0, [4]);
+        assert_eq!(empty.get_bordered_or_default([0, 0], &Border::Zero), 0);
+        assert_eq!(empty.get_bordered([0, 0], &Border::Value(5)), 5);
+
+        let m = MultiVec::<0, _>::from_fn(2, [], |i, []| NonZeroU8::new(i as u8 + 1).unwrap());
+        let one = NonZeroU8::new(1).unwrap();
+        assert_eq!(m.get_bordered([1], &Border::Value(one)).get(), 2);
+        assert_eq!(m.get_bordered([2], &Border::Value(one)).get(), 1);
+        assert_eq!(m.get_bordered([-1], &Border::Wrap).get(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Border::Zero needs T: Default")]
+    fn test_get_bordered_zero_without_default() {
+        MultiVec::<1, u8>::filled_default(2, [2]).get_bordered([0, 0], &Border::Zero);
+    }
+
+    #[test]
+    fn test_indexed_iter_mut_gradient() {
+        let mut m = MultiVec::<1, u32>::filled_default(3, [4]);
+        for ([i, j], v) in m.indexed_iter_mut() {
+            *v = (i * 10 + j) as u32;
+        }
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i * 10 + j) as u32)
+        );
+
+        let mut row = m.row_mut(1);
+        let mut cells: Vec<_> = row.indexed_iter_mut().collect();
+        assert_eq!(cells.len(), 4);
+        for ([j], v) in &mut cells {
+            **v = 100 + *j as u32;
+        }
+        assert_eq!(
+            m.row(1).iter().collect::<Vec<_>>(),
+            [&100, &101, &102, &103]
+        );
+        assert_eq!(m[[2, 3]], 23);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty grid")]
+    fn test_get_wrapping_empty() {
+        MultiVec::<1, u8>::filled_default(0, [4]).get_wrapping([0, 0]);
+    }
+
+    #[test]
+    fn test_index_coords() {
+        let mut m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        assert_eq!(m[[2, 3, 4]], (2, 3, 4));
+        assert_eq!(m[1][[0, 2]], (1, 0, 2));
+        m[[1, 2, 3]] = (0, 0, 0);
+        assert_eq!(m[1][2][3], (0, 0, 0));
+
+        let mut m = MultiVec::<0, _>::from_fn(3, [], |i, _| i);
+        m[[2]] += 1;
+        assert_eq!(m[[2]], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is out of range for dimension 1 of extent 4")]
+    fn test_index_coords_out_of_range() {
+        let m = MultiVec::<2, u8>::filled_default(3, [4, 5]);
+        _ = m[[0, 4, 0]];
+    }
+
+    #[test]
+    fn test_iter_axis() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        for (axis, extent) in [2, 3, 4].into_iter().enumerate() {
+            for index in 0..extent {
+                let expected: Vec<_> = m
+                    .indexed_iter()
+                    .filter(|(coords, _)| coords[axis] == index)
+                    .map(|(_, v)| v)
+                    .collect();
+                assert_eq!(m.iter_axis(axis, index).collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range for dimension 2")]
+    fn test_iter_axis_out_of_range() {
+        _ = MultiVec::<2, u8>::filled_default(2, [3, 4]).iter_axis(2, 4);
+    }
+
+    #[test]
+    fn test_rows() {
+        let m = MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| (i, j, k));
+        let rows: Vec<_> = m.rows().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2][[1, 0]], (2, 1, 0));
+        assert_eq!(rows[1].shape(), [2, 2]);
+        assert!(rows[1].iter().eq(m.row(1).iter()));
+        assert_eq!(m.row(1).rows().nth(1).unwrap()[0], (1, 1, 0));
+        assert_eq!(MultiVec::<1, u8>::filled_default(2, [0]).rows().count(), 2);
+    }
+
+    #[test]
+    fn test_rows_mut() {
+        let mut m = MultiVec::<2, _>::filled_default(3, [2, 2]);
+        let mut rows: Vec<_> = m.rows_mut().collect();
+        assert_eq!(rows.len(), 3);
+        let (first, rest) = rows.split_first_mut().unwrap();
+        first[[1, 1]] = 1;
+        rest[1][[0, 1]] = 2;
+        assert_eq!(m[0][1][1], 1);
+        assert_eq!(m[2][0][1], 2);
+        assert_eq!(m.sum::<i32>(), 3);
+
+        assert_eq!(
+            MultiVec::<1, u8>::filled_default(2, [0]).rows_mut().count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        let mut m = MultiVec::<1, _>::filled_default(4, [3]);
+        let (mut top, mut bottom) = m.split_at_mut(1);
+        assert_eq!(top.size(), 1);
+        assert_eq!(bottom.size(), 3);
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                top.indexed_iter_mut()
+                    .for_each(|([i, j], v)| *v = i * 3 + j)
+            });
+            scope.spawn(move || {
+                bottom
+                    .indexed_iter_mut()
+                    .for_each(|([i, j], v)| *v = (i + 1) * 3 + j)
+            });
+        });
+        assert_eq!(m, MultiVec::<1, _>::from_fn(4, [3], |i, [j]| i * 3 + j));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_split_at_mut_out_of_range() {
+        MultiVec::<1, u8>::filled_default(4, [3]).split_at_mut(5);
+    }
+
+    #[test]
+    fn test_view() {
+        let mut m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let view = m.view();
+        assert_eq!(view.shape(), [2, 3, 4]);
+        assert_eq!(view[1][2][3], (1, 2, 3));
+        assert_eq!(view[[0, 1, 2]], (0, 1, 2));
+
+        let mut view = m.view_mut();
+        view[1][0][0] = (9, 9, 9);
+        view[[0, 0, 0]] = (8, 8, 8);
+        assert_eq!(m[1][0][0], (9, 9, 9));
+        assert_eq!(m[0][0][0], (8, 8, 8));
+    }
+
+    #[test]
+    fn test_row() {
+        let mut m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| i * 12 + j * 4 + k);
+        assert_eq!(m[1].view().shape(), [3, 4]);
+        assert_eq!(m[1][2].view().shape(), [4]);
+        assert_eq!(format!("{:?}", &m[0][1]), "Row<0>([4, 5, 6, 7])");
+
+        m[1][[2, 3]] = 0;
+        m[0][1].view_mut()[[0]] = 0;
+        assert_eq!(m[[1, 2, 3]], 0);
+        assert_eq!(m[[0, 1, 0]], 0);
+    }
+
+    #[test]
+    fn test_flat_vec() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j);
+        _ = m[1][2];
+        assert_eq!(m.as_flat_vec(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(m.into_flat_vec(), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_iter_double_ended() {
+        let mut m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j);
+        assert!(m.iter().rev().copied().eq((0..6).rev()));
+        assert_eq!(m.row(1).iter().next_back(), Some(&5));
+        assert_eq!(m.iter().len(), 6);
+        assert_eq!(m.row(0).iter().len(), 3);
+
+        let mut acc = 0;
+        for v in m.iter_mut().rev() {
+            acc += *v;
+            *v = acc;
+        }
+        assert_eq!(m.as_flat_slice(), [15, 15, 14, 12, 9, 5]);
+        m.row_mut(1).iter_mut().for_each(|v| *v = 0);
+        assert_eq!(m.iter_mut().len(), 6);
+        assert_eq!(m.as_flat_slice(), [15, 15, 14, 0, 0, 0]);
+    }
 
-use crate::array::NonEmptyArrayExt;
+    #[test]
+    fn test_size_zero_inner() {
+        let m = MultiVec::<1, u8>::filled_default(3, [0]);
+        assert_eq!(m.size(), 3);
+        assert_eq!(m.view().size(), 3);
+        assert_eq!(m.shape(), [3, 0]);
+        assert!(m.is_empty());
 
-trait Placeholder {}
-impl<T> Placeholder for T {}
+        let m = MultiVec::<2, u8>::filled_default(3, [4, 0]);
+        assert_eq!(m.size(), 3);
+        assert_eq!(m.rows().count(), 3);
+        assert_eq!(MultiVec::<2, u8>::filled_default(3, [0, 4]).size(), 3);
+    }
 
-struct Refs(Mutex<Vec<*mut dyn Placeholder>>);
-unsafe impl Send for Refs {}
-unsafe impl Sync for Refs {}
+    #[test]
+    fn test_queries_without_deref() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j);
+        assert_eq!(m.size(), 2);
+        assert_eq!(m.shape(), [2, 3]);
+        assert_eq!(m.as_flat_slice(), [0, 1, 2, 3, 4, 5]);
+        assert!(m.iter().copied().eq(0..6));
 
-impl Refs {
-    fn new() -> Self {
-        Self(Default::default())
+        assert!(m.row(1).iter().copied().eq(3..6));
+        assert_eq!(m.row(1).as_flat_slice(), [3, 4, 5]);
     }
 
-    fn add(&self, ptr: *mut dyn Placeholder) {
-        self.0.lock().unwrap().push(ptr);
+    #[test]
+    fn test_push_row() {
+        let mut m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        m.push_row([(2, 0), (2, 1), (2, 2)]).unwrap();
+        assert_eq!(m.size(), 3);
+        assert_eq!(m, MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i, j)));
+
+        assert_eq!(
+            m.push_row([(3, 0), (3, 1)]),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            m.push_row([(3, 0); 4]),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            m.push_row(core::iter::repeat((3, 0))),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(m.size(), 3);
+        assert_eq!(m[2][2], (2, 2));
+
+        let mut m = MultiVec::<2, u8>::filled_default(1, [0, 4]);
+        m.push_row([]).unwrap();
+        assert_eq!(m.shape(), [2, 0, 4]);
+        assert_eq!(
+            m.push_row([0]),
+            Err(ShapeError::RowLength {
+                expected: 0,
+                actual: 1
+            })
+        );
+        assert_eq!(m.size(), 2);
     }
-}
 
-impl Drop for Refs {
-    fn drop(&mut self) {
-        for ptr in self.0.lock().unwrap().iter() {
-            _ = unsafe { Box::from_raw(*ptr) };
-        }
+    #[test]
+    fn test_insert_remove_row() {
+        let mut m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 2, j));
+        m.insert_row(1, [(1, 0), (1, 1), (1, 2)]).unwrap();
+        m.insert_row(3, (0..3).map(|j| (3, j))).unwrap();
+        m.insert_row(0, [(9, 0); 3]).unwrap();
+        assert_eq!(m.size(), 5);
+        assert_eq!(
+            m.insert_row(0, [(0, 0); 2]),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            m.insert_row(0, [(0, 0); 4]),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            m.insert_row(0, (0..).map(|j| (0, j))),
+            Err(ShapeError::RowLength {
+                expected: 3,
+                actual: 4
+            })
+        );
+
+        assert_eq!(
+            m.remove_row(0),
+            MultiVec::<0, _>::from_fn(3, [], |_, _| (9, 0))
+        );
+        assert_eq!(m, MultiVec::<1, _>::from_fn(4, [3], |i, [j]| (i, j)));
+
+        let mut m = MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| (i, j, k));
+        let row = m.remove_row(1);
+        assert_eq!(row, MultiVec::<1, _>::from_fn(2, [2], |j, [k]| (1, j, k)));
+        assert_eq!(m.shape(), [2, 2, 2]);
     }
-}
 
-struct DebugSlice<'a, T> {
-    slice: &'a [T],
-    offsets: &'a [usize],
-}
+    #[test]
+    #[should_panic(expected = "index 2 is out of range for size 2")]
+    fn test_remove_row_out_of_range() {
+        MultiVec::<1, u8>::filled_default(2, [3]).remove_row(2);
+    }
 
-impl<T: Debug> Debug for DebugSlice<'_, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Some((offset, offsets)) = self.offsets.split_first() else {
-            return f.debug_list().entries(self.slice).finish();
-        };
-        let mut list = f.debug_list();
+    #[test]
+    fn test_swap_remove_row() {
+        let mut m = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        assert_eq!(
+            m.swap_remove_row(1),
+            MultiVec::<0, _>::from_fn(2, [], |j, _| (1, j))
+        );
+        assert_eq!(
+            m.as_flat_slice(),
+            [(0, 0), (0, 1), (3, 0), (3, 1), (2, 0), (2, 1)]
+        );
+        assert_eq!(
+            m.swap_remove_row(2),
+            MultiVec::<0, _>::from_fn(2, [], |j, _| (2, j))
+        );
+        assert_eq!(m.shape(), [2, 2]);
 
-        let mut i = 0;
-        while i * offset < self.slice.len() {
-            let slice = &self.slice[i * offset..(i + 1) * offset];
-            if slice.is_empty() {
-                break;
-            }
-            list.entry(&DebugSlice { slice, offsets });
-            i += 1;
-        }
+        let mut m = MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| (i, j, k));
+        let row = m.swap_remove_row(0);
+        assert_eq!(row, MultiVec::<1, _>::from_fn(2, [2], |j, [k]| (0, j, k)));
+        assert_eq!(m[0][1][1], (2, 1, 1));
+        assert_eq!(m[1][0][0], (1, 0, 0));
+    }
 
-        list.finish()
+    #[test]
+    #[should_panic(expected = "index 3 is out of range for size 3")]
+    fn test_swap_remove_row_out_of_range() {
+        MultiVec::<1, u8>::filled_default(3, [2]).swap_remove_row(3);
     }
-}
 
-#[derive(Clone, Copy)]
-pub struct MultiVecRef<const N: usize, T> {
-    slice: *mut [T],
-    offsets: *const [usize; N],
-    refs: *const Refs,
-}
+    #[test]
+    fn test_copy_from_flat() {
+        let mut m = MultiVec::<1, u8>::filled_default(2, [3]);
+        let ptr = m.inner.as_ptr();
+        m.copy_from_flat(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j + 1) as u8)
+        );
+        assert_eq!(m.inner.as_ptr(), ptr);
 
-impl<const N: usize, T> MultiVecRef<N, T> {
-    fn slice(&self) -> &[T] {
-        unsafe { &*self.slice }
+        assert_eq!(
+            m.copy_from_flat(&[0; 5]),
+            Err(ShapeError::ElementCount {
+                expected: 6,
+                actual: 5
+            })
+        );
+        assert_eq!(m[1][2], 6);
     }
 
-    fn slice_mut(&mut self) -> &mut [T] {
-        unsafe { &mut *self.slice }
+    #[test]
+    fn test_entry() {
+        let mut m = MultiVec::<1, u32>::filled_default(3, [4]);
+        for [y, x] in [[0, 1], [2, 3], [0, 1]] {
+            *m.entry([y, x]) += 1;
+        }
+        assert_eq!(m[0][1], 2);
+        assert_eq!(m[2][3], 1);
+        assert_eq!(m.iter().sum::<u32>(), 3);
+
+        let mut m = MultiVec::<2, u32>::filled_default(2, [2, 2]);
+        *m.entry([1, 0, 1]) = 7;
+        assert_eq!(m.as_flat_slice(), [0, 0, 0, 0, 0, 7, 0, 0]);
     }
 
-    fn offsets(&self) -> &[usize; N] {
-        unsafe { &*self.offsets }
+    #[test]
+    fn test_get_two_mut() {
+        let mut m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * 4 + j);
+        let (a, b) = m.get_two_mut([2, 3], [0, 1]).unwrap();
+        core::mem::swap(a, b);
+        assert_eq!(m[0][1], 11);
+        assert_eq!(m[2][3], 1);
+
+        let (a, b) = m.get_two_mut([0, 0], [0, 1]).unwrap();
+        *a += *b;
+        assert_eq!(m[0][0], 11);
+
+        assert!(m.get_two_mut([1, 2], [1, 2]).is_none());
+        assert!(m.get_two_mut([3, 0], [0, 0]).is_none());
+        assert!(m.get_two_mut([0, 0], [0, 4]).is_none());
+        assert!(MultiVec::<1, u8>::default()
+            .get_two_mut([0, 0], [0, 1])
+            .is_none());
     }
 
-    pub fn size(&self) -> usize {
-        self.slice().len() / self.offsets().first().cloned().unwrap_or(1)
+    #[test]
+    #[should_panic(expected = "index 4 is out of range for dimension 1 of extent 4")]
+    fn test_entry_out_of_range() {
+        MultiVec::<1, u8>::filled_default(3, [4]).entry([0, 4]);
     }
-}
 
-impl<const N: usize, T> Debug for MultiVecRef<N, T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple(&format!("MultiVecRef<{N}>"))
-            .field(&DebugSlice {
-                slice: self.slice(),
-                offsets: self.offsets(),
-            })
-            .finish()
+    #[test]
+    fn test_split_outer_into() {
+        let m = MultiVec::<1, _>::from_fn(7, [2], |i, [j]| (i, j));
+        let parts = m.clone().split_outer_into(3);
+        let sizes: Vec<_> = parts.iter().map(MultiVec::size).collect();
+        assert_eq!(sizes, [3, 2, 2]);
+        assert_eq!(parts[1][0][1], (3, 1));
+        assert!(parts.iter().all(|p| p.offsets == m.offsets));
+
+        let parts = m.clone().split_outer_into(9);
+        let sizes: Vec<_> = parts.iter().map(MultiVec::size).collect();
+        assert_eq!(sizes, [1, 1, 1, 1, 1, 1, 1, 0, 0]);
+        assert_eq!(parts[8].shape(), [0, 2]);
+
+        assert_eq!(m.clone().split_outer_into(1), [m]);
     }
-}
 
-pub struct MultiVec<const N: usize, T> {
-    inner: Vec<T>,
-    offsets: [usize; N],
-    refs: Refs,
-}
+    #[test]
+    #[should_panic(expected = "number of parts must be non-zero")]
+    fn test_split_outer_into_zero() {
+        MultiVec::<1, u8>::filled_default(2, [2]).split_outer_into(0);
+    }
 
-impl<const N: usize, T: Clone> Clone for MultiVec<N, T> {
-    fn clone(&self) -> Self {
-        Self {
-            inner: self.inner.clone(),
-            offsets: self.offsets,
-            refs: Refs::new(),
+    #[test]
+    fn test_map() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as u8);
+        let doubled = m.clone().map(|v| v as u32 * 100);
+        assert_eq!(doubled.shape(), [2, 3]);
+        assert_eq!(doubled[1][2], 500);
+
+        let wide: MultiVec<1, u32> = m.map_into();
+        assert_eq!(
+            wide,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as u32)
+        );
+        assert_eq!(
+            MultiVec::<1, u8>::filled_default(3, [0])
+                .map_into::<u32>()
+                .shape(),
+            [3, 0]
+        );
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut m = MultiVec::<1, u8>::filled_default(1, [3]);
+        m.reserve(9);
+        assert!(m.capacity() >= 12);
+        let ptr = m.inner.as_ptr();
+        for _ in 0..3 {
+            m.push_row([1, 2, 3]).unwrap();
         }
+        assert_eq!(m.inner.as_ptr(), ptr);
+
+        m.shrink_to_fit();
+        assert_eq!(m.capacity(), 12);
     }
-}
 
-impl<const N: usize, T: PartialEq> PartialEq for MultiVec<N, T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.inner == other.inner && self.offsets == other.offsets
+    #[test]
+    fn test_concat_outer() {
+        let a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        let b = MultiVec::<1, _>::from_fn(1, [3], |i, [j]| (i + 2, j));
+        let c = a.concat_outer(b).unwrap();
+        assert_eq!(c, MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i, j)));
+
+        let d = MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (i, j));
+        assert_eq!(
+            c.concat_outer(d),
+            Err(ShapeError::Mismatch {
+                left: vec![3, 3],
+                right: vec![3, 2]
+            })
+        );
     }
-}
 
-impl<const N: usize, T: Eq> Eq for MultiVec<N, T> {}
+    #[test]
+    fn test_extend_from_grid() {
+        let mut a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        let b = MultiVec::<1, _>::from_fn(1, [3], |i, [j]| (i + 2, j));
+        a.extend_from_grid(&b).unwrap();
+        a.extend_from_grid(&MultiVec::<1, _>::filled_default(0, [3]))
+            .unwrap();
+        assert_eq!(a, MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i, j)));
+        assert_eq!(b.size(), 1);
 
-impl<const N: usize, T> Debug for MultiVec<N, T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple(&format!("MultiVec<{N}>"))
-            .field(&DebugSlice {
-                slice: &self.inner,
-                offsets: &self.offsets,
+        let d = MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (i, j));
+        assert_eq!(
+            a.extend_from_grid(&d),
+            Err(ShapeError::Mismatch {
+                left: vec![3, 3],
+                right: vec![3, 2]
             })
-            .finish()
+        );
+        assert_eq!(a.size(), 3);
     }
-}
 
-impl<const N: usize, T> MultiVec<N, T> {
-    pub fn default(outer_size: usize, sizes: [usize; N]) -> Self
-    where
-        T: Default + Clone,
-    {
-        Self::from_fn(outer_size, sizes, |_, _| Default::default())
+    #[test]
+    fn test_clear_truncate() {
+        let mut m = MultiVec::<1, _>::from_fn(4, [3], |i, [j]| (i, j));
+        m.truncate_outer(5);
+        assert_eq!(m.size(), 4);
+        m.truncate_outer(2);
+        assert_eq!(m, MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j)));
+
+        m.clear();
+        assert_eq!(m.size(), 0);
+        assert_eq!(m.shape(), [0, 3]);
+        assert!(m.push_row([(0, 0); 2]).is_err());
+        m.push_row([(0, 0); 3]).unwrap();
+        assert_eq!(m.shape(), [1, 3]);
     }
 
-    pub fn from_fn(
-        outer_size: usize,
-        mut sizes: [usize; N],
-        f: impl Fn(usize, [usize; N]) -> T,
-    ) -> Self {
-        let mut prod = 1;
-        let offsets = {
-            sizes.reverse();
-            let mut offsets = sizes.map(|n| {
-                prod *= n;
-                prod
-            });
-            offsets.reverse();
-            sizes.reverse();
-            offsets
-        };
+    #[test]
+    fn test_drain_outer() {
+        let mut m = MultiVec::<2, _>::from_fn(3, [2, 4], |i, [j, k]| (i, j, k));
+        let rows: Vec<_> = m.drain_outer().collect();
+        assert!(m.is_empty());
+        assert_eq!(m.shape(), [0, 2, 4]);
+        assert_eq!(rows.len(), 3);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(*row, MultiVec::<1, _>::from_fn(2, [4], |j, [k]| (i, j, k)));
+        }
 
-        let inner = [outer_size]
-            .into_iter()
-            .chain(sizes)
-            .map(|n| 0..n)
-            .multi_cartesian_product()
-            .map(|mut indices| {
-                let first = indices.remove(0);
-                f(first, indices.try_into().unwrap())
-            })
-            .collect();
+        let mut m = MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (i, j));
+        assert_eq!(m.drain_outer().count(), 3);
+        assert!(m.is_empty());
+    }
 
-        Self {
-            offsets,
-            inner,
-            refs: Refs::new(),
+    #[test]
+    fn test_retain_outer() {
+        let mut m = MultiVec::<2, _>::from_fn(5, [2, 3], |i, [j, k]| i * 6 + j * 3 + k);
+        m.retain_outer(|row: MultiVecRef<'_, 1, _>| row[0][0] % 12 == 0);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(3, [2, 3], |i, [j, k]| i * 12 + j * 3 + k)
+        );
+        assert_eq!(m.inner.len() % 6, 0);
+
+        m.retain_outer(|_| false);
+        assert_eq!(m.shape(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn test_resize_outer() {
+        let mut m = MultiVec::<2, _>::filled_default(2, [3, 4]);
+        m.resize_outer(5, 1);
+        assert_eq!(m.size(), 5);
+        assert_eq!(m[1][2][3], 0);
+        assert_eq!(m[4][2][3], 1);
+
+        m.resize_outer(0, 1);
+        assert_eq!(m.size(), 0);
+        assert_eq!(m, MultiVec::<2, _>::filled_default(0, [3, 4]));
+
+        for n in [3, 1, 4, 0, 2] {
+            m.resize_outer(n, 2);
+            assert_eq!(m.size(), n);
         }
+        assert_eq!(m, MultiVec::<2, _>::from_fn(2, [3, 4], |_, _| 2));
     }
-}
 
-impl<const N: usize, T> Deref for MultiVec<N, T> {
-    type Target = MultiVecRef<N, T>;
+    #[test]
+    fn test_argmax() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i * 4 + j) % 5);
+        assert_eq!(m.argmax(), Some([1, 0]));
+        assert_eq!(m.argmin(), Some([0, 0]));
+        assert_eq!(m.row(2).argmax(), Some([1]));
+        assert_eq!(m.row(2).argmin(), Some([2]));
+        assert_eq!(MultiVec::<1, u8>::filled_default(0, [4]).argmax(), None);
+        assert_eq!(MultiVec::<1, u8>::filled_default(0, [4]).argmin(), None);
+    }
 
-    fn deref(&self) -> &Self::Target {
-        let ptr = Box::into_raw(Box::new(MultiVecRef {
-            slice: self.inner.as_slice() as *const _ as *mut _,
-            offsets: &self.offsets,
-            refs: &self.refs,
-        }));
-        self.refs.add(ptr as *mut dyn Placeholder);
-        unsafe { &*ptr }
+    #[test]
+    fn test_max_min() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i * 7 + j * 5) % 11);
+        assert_eq!(m.max_element(), Some(&10));
+        assert_eq!(m.min_element(), Some(&0));
+        assert_eq!(m.row(1).max_element(), Some(&7));
+        assert_eq!(m.row(1).min_element(), Some(&0));
+        assert_eq!(MultiVec::<1, u8>::default().max_element(), None);
+        assert_eq!(MultiVec::<1, u8>::default().min_element(), None);
     }
-}
 
-impl<const N: usize, T> DerefMut for MultiVec<N, T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        let ptr = Box::into_raw(Box::new(MultiVecRef {
-            slice: self.inner.as_mut_slice() as *mut _,
-            offsets: &self.offsets,
-            refs: &self.refs,
-        }));
-        self.refs.add(ptr as *mut dyn Placeholder);
-        unsafe { &mut *ptr }
+    #[test]
+    fn test_fold() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * 4 + j + 1);
+        assert_eq!(m.fold(0, |acc, v| acc + v), 78);
+        assert_eq!(m.row(1).fold(0, |acc, v| acc + v), 26);
+        assert_eq!(m.reduce(|a, b| *a.max(b)), Some(12));
+        assert_eq!(m.sum::<usize>(), 78);
+        assert_eq!(m.row(0).product::<usize>(), 24);
+
+        let empty = MultiVec::<1, usize>::filled_default(0, [4]);
+        assert_eq!(empty.reduce(|a, b| a + b), None);
+        assert_eq!(empty.sum::<usize>(), 0);
     }
-}
 
-impl<T> Index<usize> for MultiVecRef<0, T> {
-    type Output = T;
+    #[test]
+    fn test_debug() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * 3 + j);
+        assert_eq!(format!("{m:?}"), "MultiVec<1>([[0, 1, 2], [3, 4, 5]])");
+        assert_eq!(format!("{:?}", m.row(1)), "MultiVecRef<0>([3, 4, 5])");
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.slice()[index]
+        let m = MultiVec::<0, _>::from_fn(2000, [], |i, _| i);
+        assert_eq!(
+            format!("{m:?}"),
+            "MultiVec<0>([0, 1, 2, ..., 1997, 1998, 1999])"
+        );
+        assert_eq!(format!("{m:#?}").lines().count(), 2004);
+
+        let m = MultiVec::<1, _>::from_fn(100, [20], |i, [j]| i * 20 + j);
+        assert_eq!(
+            format!("{m:?}"),
+            "MultiVec<1>([[0, 1, 2, ..., 17, 18, 19], [20, 21, 22, ..., 37, 38, 39], \
+             [40, 41, 42, ..., 57, 58, 59], ..., [1940, 1941, 1942, ..., 1957, 1958, 1959], \
+             [1960, 1961, 1962, ..., 1977, 1978, 1979], [1980, 1981, 1982, ..., 1997, 1998, 1999]])"
+        );
+        assert_eq!(format!("{:?}", m.row(0)), "MultiVecRef<0>([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19])");
     }
-}
 
-impl<T> IndexMut<usize> for MultiVecRef<0, T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.slice_mut()[index]
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn test_ord() {
+        let a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i + j);
+        let b = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * j);
+        let wide = MultiVec::<1, _>::from_fn(1, [4], |_, _| 0);
+        let tall = MultiVec::<1, _>::from_fn(3, [3], |_, _| 0);
+        assert!(b < a);
+        assert!(a < wide);
+        assert!(tall < a);
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+
+        let short = MultiVec::<1, u8>::filled_default(2, [0]);
+        let long = MultiVec::<1, u8>::filled_default(3, [0]);
+        assert_ne!(short, long);
+        assert_eq!(short.cmp(&long), Ordering::Less);
+
+        let set: std::collections::BTreeSet<_> = [a.clone(), b.clone(), a.clone()].into();
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), [b, a]);
     }
-}
 
-macro_rules! impl_index {
-    ($n:expr) => {
-        impl<T: Debug> Index<usize> for MultiVecRef<$n, T> {
-            type Output = MultiVecRef<{ $n - 1 }, T>;
+    #[test]
+    fn test_default() {
+        let m = MultiVec::<2, u8>::default();
+        assert_eq!(format!("{m:?}"), "MultiVec<2>([])");
+        assert_eq!(m, MultiVec::<2, _>::filled_default(0, [0, 0]));
+        assert_eq!(MultiVec::<0, u8>::default().size(), 0);
+    }
 
-            fn index(&self, index: usize) -> &Self::Output {
-                let (offset, offsets) = self.offsets().arr_split_first();
-                let ptr = Box::into_raw(Box::new(MultiVecRef {
-                    slice: &self.slice()[index * offset..(index + 1) * offset] as *const _
-                        as *mut _,
-                    offsets,
-                    refs: self.refs,
-                }));
-                let refs = unsafe { &*self.refs };
-                refs.add(ptr as *mut dyn Placeholder);
-                unsafe { &*ptr }
-            }
-        }
+    #[test]
+    fn test_map_inplace() {
+        let mut m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32 - 2);
+        let ptr = m.inner.as_ptr();
+        m.map_inplace(|v| *v = (*v).clamp(0, 2));
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| ((i * 3 + j) as i32 - 2).clamp(0, 2))
+        );
+        assert_eq!(m.inner.as_ptr(), ptr);
+    }
 
-        impl<T: Debug> IndexMut<usize> for MultiVecRef<$n, T> {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                let MultiVecRef {
-                    slice,
-                    offsets,
-                    refs,
-                } = self;
-                let slice = unsafe { &mut **slice };
-                let offsets = unsafe { &**offsets };
-                let (offset, offsets) = offsets.arr_split_first();
-                let offset = *offset;
-                let ptr = Box::into_raw(Box::new(MultiVecRef {
-                    slice: &mut slice[index * offset..(index + 1) * offset] as *mut _,
-                    offsets,
-                    refs: *refs,
-                }));
-                let refs = unsafe { &*self.refs };
-                refs.add(ptr as *mut dyn Placeholder);
-                unsafe { &mut *ptr }
-            }
+    #[test]
+    fn test_for_each_mut() {
+        let mut m = MultiVec::<2, _>::filled_default(3, [4, 5]);
+        m.for_each_mut(|[i, j, k], v| *v = (i, j, k));
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k))
+        );
+
+        let mut visited = Vec::new();
+        MultiVec::<1, _>::from_fn(2, [2], |_, _| ()).for_each_mut(|c, _| visited.push(c));
+        assert_eq!(visited, [[0, 0], [0, 1], [1, 0], [1, 1]]);
+    }
+
+    #[test]
+    fn test_windows() {
+        let m = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        let windows: Vec<_> = m.windows(3).collect();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].size(), 3);
+        assert_eq!(windows[1][0][1], (1, 1));
+        assert_eq!(windows[1][2][0], (3, 0));
+        assert!(windows[0].iter().eq(&m.as_flat_slice()[..6]));
+
+        assert_eq!(m.windows(4).count(), 1);
+        assert_eq!(m.windows(5).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn test_windows_zero() {
+        let m = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        _ = m.windows(0);
+    }
+
+    #[test]
+    fn test_high_rank() {
+        let m = MultiVec::<19, u8>::filled_default(1, [1; 19]);
+        assert_eq!(m.shape(), [1; 20]);
+        assert_eq!(m[[0; 20]], 0);
+    }
+
+    #[test]
+    fn test_zero_extents() {
+        for (sizes, debug) in [
+            ([0, 3, 4], "[]"),
+            ([2, 0, 4], "[[], []]"),
+            ([2, 3, 0], "[[[], [], []], [[], [], []]]"),
+        ] {
+            let mut m = MultiVec::<2, u8>::filled_default(sizes[0], [sizes[1], sizes[2]]);
+            assert!(m.is_empty());
+            assert_eq!(m.size(), sizes[0]);
+            assert_eq!(m.view().size(), sizes[0]);
+            assert_eq!(m.shape(), sizes);
+            assert_eq!(m.iter().count(), 0);
+            assert_eq!(m.indexed_iter::<3>().count(), 0);
+            assert_eq!(m.windows(1).count(), sizes[0]);
+            assert_eq!(m.chunks_outer(1).count(), sizes[0]);
+            assert_eq!(m.get_flat(0), None);
+            assert_eq!(m.rows_mut().count(), sizes[0]);
+            assert_eq!(m.view_mut().split_at_mut(0).1.size(), sizes[0]);
+            assert_eq!(format!("{m:?}"), format!("MultiVec<2>({debug})"));
         }
-    };
-}
+    }
 
-impl_index!(1);
-impl_index!(2);
-impl_index!(3);
-impl_index!(4);
-impl_index!(5);
-impl_index!(6);
-impl_index!(7);
-impl_index!(8);
-impl_index!(9);
+    #[test]
+    #[should_panic(expected = "index 0 is out of range for dimension 2 of extent 0")]
+    fn test_zero_extent_index() {
+        let m = MultiVec::<2, u8>::filled_default(2, [3, 0]);
+        _ = m[[1, 2, 0]];
+    }
 
-#[cfg(test)]
-mod test {
-    use proptest::prelude::*;
+    #[test]
+    fn test_strides() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        let strides = m.strides();
+        assert_eq!(strides, [20, 5]);
+        assert_eq!(m.row(1).strides(), [5]);
+        assert_eq!(m.inner[2 * strides[0] + 3 * strides[1] + 4], (2, 3, 4));
+    }
 
-    use crate::MultiVec;
+    #[test]
+    fn test_view_sub() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        assert_eq!(
+            m.view_sub([1, 1, 2], [2, 2, 3]),
+            MultiVec::<2, _>::from_fn(2, [2, 3], |i, [j, k]| (i + 1, j + 1, k + 2))
+        );
+        assert_eq!(m.view_sub([0, 0, 0], [3, 4, 5]), m);
+        assert!(m.view_sub([3, 4, 5], [0, 0, 0]).is_empty());
+        assert_eq!(
+            m.row(2).view_sub([3, 0], [1, 5]),
+            MultiVec::<1, _>::from_fn(1, [5], |_, [k]| (2, 3, k))
+        );
+    }
 
-    const _: () = {
-        const fn assert_send<T: Send>() {}
-        const fn assert_sync<T: Sync>() {}
-        assert_send::<MultiVec<0, u8>>();
-        assert_sync::<MultiVec<0, u8>>();
-    };
+    #[test]
+    #[should_panic(
+        expected = "region of length 3 at 3 is out of range for dimension 2 of extent 5"
+    )]
+    fn test_view_sub_out_of_range() {
+        let m = MultiVec::<2, u8>::filled_default(3, [4, 5]);
+        m.view_sub([0, 0, 3], [1, 1, 3]);
+    }
 
-    struct NonClone<T>(T);
+    #[test]
+    fn test_same_shape() {
+        let a = MultiVec::<2, u8>::filled_default(2, [3, 4]);
+        let b = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        assert!(a.same_shape(&b));
+        assert!(a.row(0).same_shape(&b.row(1)));
+        assert!(!a.same_shape(&MultiVec::<2, u8>::filled_default(3, [3, 4])));
+        assert!(!a.same_shape(&MultiVec::<2, u8>::filled_default(2, [4, 3])));
+        assert!(MultiVec::<2, u8>::filled_default(0, [3, 4])
+            .same_shape(&MultiVec::<2, u8>::filled_default(0, [3, 4])));
+        assert!(!MultiVec::<2, u8>::filled_default(0, [3, 4])
+            .same_shape(&MultiVec::<2, u8>::filled_default(0, [4, 3])));
+    }
 
     #[test]
-    fn test_thread_move() {
-        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| NonClone((i, j, k)));
-        std::thread::scope(|scope| {
-            scope.spawn(move || {
-                drop(m);
-            });
+    fn test_inner_shape() {
+        let a = MultiVec::<2, u8>::filled_default(2, [3, 4]);
+        assert_eq!(a.inner_shape(), [3, 4]);
+        assert_eq!(a.row(1).inner_shape(), [4]);
+        assert_eq!(MultiVec::<0, u8>::filled_default(5, []).inner_shape(), []);
+        assert_eq!(
+            MultiVec::<2, u8>::filled_default(0, [0, 4]).inner_shape(),
+            [0, 4]
+        );
+
+        let b = MultiVec::<2, _>::from_fn(7, [3, 4], |i, [j, k]| (i, j, k));
+        assert!(a.inner_shape_eq(&b));
+        assert!(!a.same_shape(&b));
+        assert!(!a.inner_shape_eq(&MultiVec::<2, u8>::filled_default(2, [4, 3])));
+    }
+
+    #[test]
+    fn test_to_owned() {
+        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| (i, j, k));
+        let mut sub = m.row(1).to_owned();
+        assert_eq!(sub.shape(), m.row(1).shape());
+        assert_eq!(sub, MultiVec::<1, _>::from_fn(4, [5], |j, [k]| (1, j, k)));
+
+        sub[0][0] = (9, 9, 9);
+        assert_eq!(m[1][0][0], (1, 0, 0));
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let m = MultiVec::<2, u8>::filled_default(3, [4, 5]);
+        assert!(!m.is_empty());
+        assert_eq!(m.element_count(), 60);
+        assert_eq!(m.row(1).element_count(), 20);
+
+        for (outer_size, sizes) in [(0, [4, 5]), (3, [0, 5]), (3, [4, 0])] {
+            let m = MultiVec::<2, u8>::filled_default(outer_size, sizes);
+            assert!(m.is_empty());
+            assert_eq!(m.element_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let source = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| i * j);
+        let mut m = MultiVec::<1, _>::filled_default(4, [3]);
+        let ptr = m.inner.as_ptr();
+        m.clone_from(&source);
+        assert_eq!(m, source);
+        assert_eq!(m.inner.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_try_from_fn_overflow() {
+        assert_eq!(
+            MultiVec::<2, u8>::try_from_fn::<ShapeError>(1, [usize::MAX, 2], |_, _| {
+                unreachable!()
+            }),
+            Err(ShapeError::Overflow)
+        );
+        assert_eq!(
+            MultiVec::<1, u8>::try_from_fn::<ShapeError>(usize::MAX, [2], |_, _| unreachable!()),
+            Err(ShapeError::Overflow)
+        );
+        assert_eq!(
+            MultiVec::<2, usize>::try_from_fn::<ShapeError>(2, [3, 4], |i, [j, k]| Ok(i + j + k)),
+            Ok(MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| i + j + k))
+        );
+    }
+
+    #[test]
+    fn test_from_fn_coords() {
+        assert_eq!(
+            MultiVec::<2, _>::from_fn_coords([2, 3, 4], |[i, j, k]| i * 100 + j * 10 + k),
+            MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| i * 100 + j * 10 + k)
+        );
+        assert_eq!(MultiVec::<0, _>::from_fn_coords([3], |[i]| i).shape(), [3]);
+    }
+
+    #[test]
+    fn test_from_fn_stateful() {
+        let mut counter = 0;
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |_, _| {
+            counter += 1;
+            counter
         });
+        assert_eq!(counter, 24);
+        assert!(m.iter().copied().eq(1..=24));
+
+        let mut order = Vec::new();
+        MultiVec::<1, _>::from_fn_coords([2, 2], |c| order.push(c));
+        assert_eq!(order, [[0, 0], [0, 1], [1, 0], [1, 1]]);
     }
 
     #[test]
-    fn test_thread_ref() {
-        let m = MultiVec::<2, _>::from_fn(3, [4, 5], |i, [j, k]| NonClone((i, j, k)));
-        std::thread::scope(|scope| {
-            scope.spawn(|| &m);
-            scope.spawn(|| &m);
+    #[should_panic(expected = "shape is too large")]
+    fn test_from_fn_overflow() {
+        MultiVec::<1, u8>::from_fn(usize::MAX, [2], |_, _| unreachable!());
+    }
+
+    #[test]
+    fn test_try_from_fn_error() {
+        let visited = core::cell::RefCell::new(Vec::new());
+        let result = MultiVec::<1, String>::try_from_fn(3, [2], |i, [j]| {
+            visited.borrow_mut().push([i, j]);
+            match i * 2 + j {
+                3 => Err(ShapeError::RowLength {
+                    expected: 2,
+                    actual: 3,
+                }),
+                n => Ok(n.to_string()),
+            }
         });
+        assert_eq!(
+            result,
+            Err(ShapeError::RowLength {
+                expected: 2,
+                actual: 3
+            })
+        );
+        assert_eq!(*visited.borrow(), [[0, 0], [0, 1], [1, 0], [1, 1]]);
+    }
+
+    #[test]
+    fn test_shape() {
+        let m = MultiVec::<2, u8>::filled_default(3, [4, 5]);
+        assert_eq!(m.shape(), [3, 4, 5]);
+        assert_eq!(m.row(0).shape(), [4, 5]);
+        assert_eq!(m.row(0).row(0).shape(), [5]);
+        assert_eq!(
+            MultiVec::<2, u8>::filled_default(0, [4, 5]).shape(),
+            [0, 4, 5]
+        );
+        assert_eq!(
+            MultiVec::<2, u8>::filled_default(3, [0, 5]).shape(),
+            [3, 0, 5]
+        );
+        assert_eq!(
+            MultiVec::<2, u8>::filled_default(3, [5, 0]).shape(),
+            [3, 5, 0]
+        );
+        assert_eq!(
+            MultiVec::<2, u8>::filled_default(3, [0, 5]).row(2).shape(),
+            [0, 5]
+        );
     }
 
     proptest! {
@@ -339,7 +2684,7 @@ mod test {
                 }
             }
 
-            let mut m2 = MultiVec::<2, _>::default(outer_size, sizes);
+            let mut m2 = MultiVec::<2, _>::filled_default(outer_size, sizes);
             for i1 in 0..outer_size {
                 for i2 in 0..sizes[0] {
                     for i3 in 0..sizes[1] {
@@ -350,5 +2695,27 @@ mod test {
 
             prop_assert_eq!(m1, m2);
         }
+
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn test_indexed_iter(outer_size in 0..10usize, sizes in prop::array::uniform2(0..10usize)) {
+            let mut m = MultiVec::<2, _>::from_fn(outer_size, sizes, |i1, [i2, i3]| (i1, i2, i3));
+            let mut count = 0;
+            let mut last = None;
+            for ([i1, i2, i3], v) in m.indexed_iter() {
+                prop_assert_eq!(m[i1][i2][i3], *v);
+                count += 1;
+                last = Some([i1, i2, i3]);
+            }
+            prop_assert_eq!(count, outer_size * sizes[0] * sizes[1]);
+            if count > 0 {
+                prop_assert_eq!(last, Some([outer_size - 1, sizes[0] - 1, sizes[1] - 1]));
+            }
+
+            for ([i1, i2, i3], v) in m.indexed_iter_mut() {
+                *v = (i3, i2, i1);
+            }
+            prop_assert_eq!(m, MultiVec::<2, _>::from_fn(outer_size, sizes, |i1, [i2, i3]| (i3, i2, i1)));
+        }
     }
 }
@@ -0,0 +1,285 @@
+use core::ops::{Add, Mul};
+
+use num_traits::{One, Zero};
+
+use crate::{MultiVec, ShapeError};
+
+impl<T> MultiVec<1, T> {
+    pub fn identity(n: usize) -> Self
+    where
+        T: Zero + One,
+    {
+        Self::from_fn(n, [n], |i, [j]| if i == j { T::one() } else { T::zero() })
+    }
+
+    pub fn diagonal(&self) -> impl Iterator<Item = &T> {
+        let [rows, cols] = self.shape();
+        (0..rows.min(cols)).map(move |i| &self.inner[i * cols + i])
+    }
+
+    pub fn fill_diagonal(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        let [rows, cols] = self.shape();
+        for i in 0..rows.min(cols) {
+            self.inner[i * cols + i] = value.clone();
+        }
+    }
+
+    pub fn trace(&self) -> T
+    where
+        T: Add<Output = T> + Default + Copy,
+    {
+        let [rows, cols] = self.shape();
+        assert!(
+            rows == cols,
+            "trace requires a square matrix, got shape [{rows}, {cols}]"
+        );
+        self.diagonal().fold(T::default(), |acc, v| acc + *v)
+    }
+
+    pub fn tile(&self, rows: usize, cols: usize) -> Self
+    where
+        T: Clone,
+    {
+        let [h, w] = self.shape();
+        let overflow = || panic!("{}", ShapeError::Overflow);
+        let height = h.checked_mul(rows).unwrap_or_else(overflow);
+        let width = w.checked_mul(cols).unwrap_or_else(overflow);
+        MultiVec::from_fn(height, [width], |i, [j]| {
+            self.inner[(i % h) * w + j % w].clone()
+        })
+    }
+
+    pub fn sum_axis(&self, axis: usize) -> MultiVec<0, T>
+    where
+        T: Add<Output = T> + Default + Copy,
+    {
+        let [rows, cols] = self.shape();
+        match axis {
+            0 => MultiVec::from_fn(cols, [], |j, []| {
+                (0..rows).fold(T::default(), |acc, i| acc + self.inner[i * cols + j])
+            }),
+            1 => MultiVec::from_fn(rows, [], |i, []| {
+                (0..cols).fold(T::default(), |acc, j| acc + self.inner[i * cols + j])
+            }),
+            _ => panic!("axis {axis} is out of range for rank 2"),
+        }
+    }
+
+    pub fn matmul(&self, other: &MultiVec<1, T>) -> Result<MultiVec<1, T>, ShapeError>
+    where
+        T: Mul<Output = T> + Add<Output = T> + Default + Copy,
+    {
+        let [rows, len] = self.shape();
+        let [other_rows, cols] = other.shape();
+        if len != other_rows {
+            return Err(ShapeError::Mismatch {
+                left: [rows, len].into(),
+                right: [other_rows, cols].into(),
+            });
+        }
+
+        Ok(MultiVec::from_fn(rows, [cols], |i, [j]| {
+            (0..len).fold(T::default(), |acc, k| {
+                acc + self.inner[i * len + k] * other.inner[k * cols + j]
+            })
+        }))
+    }
+
+    /// Centres `kernel` on every cell and sums the products with the cells under it, treating
+    /// cells outside the grid as zero. The kernel is not flipped: for a `k_rows` × `k_cols` kernel,
+    /// `kernel[di][dj]` weighs the cell `di - k_rows / 2` rows down and `dj - k_cols / 2` columns
+    /// right of the centre.
+    pub fn apply_stencil(&self, kernel: &MultiVec<1, f64>) -> MultiVec<1, f64>
+    where
+        T: Clone + Into<f64>,
+    {
+        let [rows, cols] = self.shape();
+        let [k_rows, k_cols] = kernel.shape();
+        assert!(
+            k_rows % 2 == 1 && k_cols % 2 == 1,
+            "kernel shape [{k_rows}, {k_cols}] must have odd dimensions"
+        );
+
+        MultiVec::from_fn(rows, [cols], |i, [j]| {
+            let mut acc = 0.0;
+            for di in 0..k_rows {
+                let Some(y) = (i + di).checked_sub(k_rows / 2).filter(|y| *y < rows) else {
+                    continue;
+                };
+                for dj in 0..k_cols {
+                    let Some(x) = (j + dj).checked_sub(k_cols / 2).filter(|x| *x < cols) else {
+                        continue;
+                    };
+                    acc += kernel.inner[di * k_cols + dj] * self.inner[y * cols + x].clone().into();
+                }
+            }
+            acc
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{MultiVec, ShapeError};
+
+    #[test]
+    fn test_matmul() {
+        let a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32);
+        let b = MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (i * 2 + j) as i32 - 2);
+        let c = a.matmul(&b).unwrap();
+        assert_eq!(c.shape(), [2, 2]);
+        assert_eq!([c[0][0], c[0][1], c[1][0], c[1][1]], [4, 7, 4, 16]);
+
+        assert_eq!(
+            a.matmul(&a),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 3],
+                right: vec![2, 3]
+            })
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        let id = MultiVec::<1, i32>::identity(3);
+        assert_eq!(
+            id,
+            MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i == j) as i32)
+        );
+
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32);
+        assert_eq!(m.matmul(&id), Ok(m.clone()));
+        assert_eq!(MultiVec::identity(2).matmul(&m), Ok(m));
+        assert_eq!(MultiVec::<1, i32>::identity(0).shape(), [0, 0]);
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let m = MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i, j));
+        assert!(m.diagonal().eq(&[(0, 0), (1, 1), (2, 2)]));
+
+        let wide = MultiVec::<1, _>::from_fn(2, [4], |i, [j]| (i, j));
+        assert!(wide.diagonal().eq(&[(0, 0), (1, 1)]));
+        let tall = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        assert!(tall.diagonal().eq(&[(0, 0), (1, 1)]));
+
+        let mut m = MultiVec::<1, _>::filled_default(2, [3]);
+        m.fill_diagonal(1);
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i == j) as i32)
+        );
+        assert_eq!(MultiVec::<1, u8>::default().diagonal().count(), 0);
+    }
+
+    #[test]
+    fn test_trace() {
+        let m = MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i * 3 + j) as i32);
+        assert_eq!(m.trace(), 12);
+        assert_eq!(m.trace(), m.diagonal().sum());
+        assert_eq!(MultiVec::<1, i32>::identity(4).trace(), 4);
+        assert_eq!(MultiVec::<1, i32>::default().trace(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "trace requires a square matrix, got shape [2, 3]")]
+    fn test_trace_not_square() {
+        MultiVec::<1, i32>::filled_default(2, [3]).trace();
+    }
+
+    #[test]
+    fn test_tile() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        let tiled = m.tile(3, 2);
+        assert_eq!(tiled.shape(), [6, 6]);
+        assert_eq!(
+            tiled,
+            MultiVec::<1, _>::from_fn(6, [6], |i, [j]| (i % 2, j % 3))
+        );
+        assert_eq!(m.tile(0, 2).shape(), [0, 6]);
+        assert!(m.tile(2, 0).is_empty());
+        assert_eq!(MultiVec::<1, u8>::default().tile(2, 2).shape(), [0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "shape is too large")]
+    fn test_tile_overflow() {
+        MultiVec::<1, u8>::filled_default(2, [3]).tile(1, usize::MAX);
+    }
+
+    #[test]
+    fn test_sum_axis() {
+        let m = MultiVec::<1, _>::from_fn(3, [4], |i, [j]| (i * 4 + j) as i32);
+        let columns = m.sum_axis(0);
+        assert_eq!(columns.shape(), [4]);
+        for j in 0..4 {
+            assert_eq!(columns[j], (0..3).map(|i| m[i][j]).sum::<i32>());
+        }
+        let rows = m.sum_axis(1);
+        assert_eq!(rows.shape(), [3]);
+        for i in 0..3 {
+            assert_eq!(rows[i], (0..4).map(|j| m[i][j]).sum::<i32>());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "axis 2 is out of range for rank 2")]
+    fn test_sum_axis_invalid() {
+        MultiVec::<1, i32>::filled_default(2, [2]).sum_axis(2);
+    }
+
+    #[test]
+    fn test_apply_stencil() {
+        let m = MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i * 3 + j + 1) as u8);
+        let blur = MultiVec::<1, _>::from_fn(3, [3], |_, _| 1.0 / 9.0);
+        let expected = MultiVec::<1, _>::from_fn(3, [3], |i, [j]| {
+            [[12.0, 21.0, 16.0], [27.0, 45.0, 33.0], [24.0, 39.0, 28.0]][i][j] / 9.0
+        });
+        assert!(m.apply_stencil(&blur).approx_eq(&expected, 1e-12));
+
+        let right = MultiVec::<1, _>::from_fn(1, [3], |_, [j]| (j == 2) as u8 as f64);
+        assert_eq!(
+            m.apply_stencil(&right),
+            MultiVec::<1, _>::from_fn(3, [3], |i, [j]| match j {
+                2 => 0.0,
+                _ => (i * 3 + j + 2) as f64,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must have odd dimensions")]
+    fn test_apply_stencil_even() {
+        let m = MultiVec::<1, u8>::filled_default(3, [3]);
+        m.apply_stencil(&MultiVec::<1, _>::filled_default(2, [3]));
+    }
+
+    proptest! {
+        #[test]
+        fn test_matmul_naive(
+            m in 0..6usize,
+            k in 0..6usize,
+            n in 0..6usize,
+            seed in any::<i64>(),
+        ) {
+            let a = MultiVec::<1, _>::from_fn(m, [k], |i, [j]| (seed ^ (i * 7 + j) as i64) % 100);
+            let b = MultiVec::<1, _>::from_fn(k, [n], |i, [j]| (seed ^ (i * 5 + j) as i64) % 100);
+            let c = a.matmul(&b).unwrap();
+            prop_assert_eq!(c.shape(), [m, n]);
+            for i in 0..m {
+                for j in 0..n {
+                    let mut expected = 0;
+                    for l in 0..k {
+                        expected += a[i][l] * b[l][j];
+                    }
+                    prop_assert_eq!(c[i][j], expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use ::ndarray::{ArrayD, IxDyn};
+
+use crate::{MultiVec, ShapeError};
+
+impl<const N: usize, T> MultiVec<N, T> {
+    pub fn to_ndarray(&self) -> ArrayD<T>
+    where
+        T: Clone,
+    {
+        ArrayD::from_shape_vec(IxDyn(&self.shape_vec()), self.inner.clone()).unwrap()
+    }
+
+    pub fn try_from_ndarray(array: ArrayD<T>) -> Result<Self, ShapeError> {
+        if array.ndim() != N + 1 {
+            return Err(ShapeError::Rank {
+                expected: N + 1,
+                actual: array.ndim(),
+            });
+        }
+        let outer_size = array.shape()[0];
+        let sizes: [usize; N] = array.shape()[1..].try_into().unwrap();
+        let inner = if array.is_standard_layout() {
+            // The elements are already in row-major order, just maybe not at the start.
+            let len = array.len();
+            let (mut inner, offset) = array.into_raw_vec_and_offset();
+            let offset = offset.unwrap_or(0);
+            inner.truncate(offset + len);
+            inner.drain(..offset);
+            inner
+        } else {
+            array.into_iter().collect()
+        };
+        Ok(Self::from_parts(inner, outer_size, sizes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::ndarray::{s, ArrayD, IxDyn};
+
+    use crate::{MultiVec, ShapeError};
+
+    #[test]
+    fn test_to_ndarray() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let array = m.to_ndarray();
+        assert_eq!(array.shape(), [2, 3, 4]);
+        assert_eq!(array[[1, 2, 3]], (1, 2, 3));
+        let ptr = array.as_ptr();
+        let back = MultiVec::try_from_ndarray(array).unwrap();
+        assert_eq!(back.as_flat_slice().as_ptr(), ptr);
+        assert_eq!(back, m);
+    }
+
+    #[test]
+    fn test_try_from_ndarray_rank() {
+        let array = ArrayD::from_elem(IxDyn(&[2, 3]), 0);
+        assert_eq!(
+            MultiVec::<2, _>::try_from_ndarray(array),
+            Err(ShapeError::Rank {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_from_ndarray_transposed() {
+        let array = ArrayD::from_shape_fn(IxDyn(&[3, 2]), |d| (d[1], d[0]));
+        let m = MultiVec::<1, _>::try_from_ndarray(array.t().to_owned()).unwrap();
+        assert_eq!(m, MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j)));
+    }
+
+    #[test]
+    fn test_try_from_ndarray_sliced() {
+        let mut array = ArrayD::from_shape_fn(IxDyn(&[4, 3]), |d| (d[0], d[1]));
+        array.slice_collapse(s![1..3, ..]);
+        assert!(array.is_standard_layout());
+        let m = MultiVec::<1, _>::try_from_ndarray(array).unwrap();
+        assert_eq!(m, MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i + 1, j)));
+    }
+}
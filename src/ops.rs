@@ -0,0 +1,234 @@
+use core::ops::{Add, Mul, Sub};
+
+use crate::{MultiVec, NonEmptyArray, NonEmptyArrayExt, ShapeError};
+
+macro_rules! impl_op {
+    ($trait:ident, $method:ident, $checked:ident) => {
+        impl<const N: usize, T> MultiVec<N, T>
+        where
+            T: $trait<Output = T> + Copy,
+        {
+            pub fn $checked(&self, rhs: &Self) -> Result<Self, ShapeError> {
+                if !self.same_shape(rhs) {
+                    return Err(self.mismatch(rhs));
+                }
+                Ok(Self::from_parts(
+                    self.inner
+                        .iter()
+                        .zip(&rhs.inner)
+                        .map(|(a, b)| $trait::$method(*a, *b))
+                        .collect(),
+                    self.outer,
+                    self.sizes,
+                ))
+            }
+        }
+
+        impl<const N: usize, T> $trait for &MultiVec<N, T>
+        where
+            T: $trait<Output = T> + Copy,
+        {
+            type Output = MultiVec<N, T>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.$checked(rhs).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+
+        impl<const N: usize, T> $trait<T> for MultiVec<N, T>
+        where
+            T: $trait<Output = T> + Copy,
+        {
+            type Output = Self;
+
+            fn $method(mut self, rhs: T) -> Self::Output {
+                for v in &mut self.inner {
+                    *v = $trait::$method(*v, rhs);
+                }
+                self
+            }
+        }
+    };
+}
+
+impl<const N: usize, T> MultiVec<N, T> {
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool
+    where
+        T: Sub<Output = T> + PartialOrd + Copy,
+    {
+        self.same_shape(other)
+            && self.inner.iter().zip(&other.inner).all(|(&a, &b)| {
+                let diff = if a > b { a - b } else { b - a };
+                diff <= epsilon
+            })
+    }
+
+    pub fn broadcast_with<const N_MINUS_ONE: usize, U, R>(
+        &self,
+        other: &MultiVec<N_MINUS_ONE, U>,
+        f: impl Fn(&T, &U) -> R,
+    ) -> Result<MultiVec<N, R>, ShapeError>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let (size, sizes) = self.sizes.arr_split_first();
+        if *size != other.outer || *sizes != other.sizes {
+            return Err(ShapeError::Mismatch {
+                left: self.shape_vec(),
+                right: other.shape_vec(),
+            });
+        }
+        Ok(MultiVec::from_parts(
+            self.inner
+                .iter()
+                .zip(other.inner.iter().cycle())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            self.outer,
+            self.sizes,
+        ))
+    }
+
+    pub fn zip_mut_with<U>(
+        &mut self,
+        other: &MultiVec<N, U>,
+        mut f: impl FnMut(&mut T, &U),
+    ) -> Result<(), ShapeError> {
+        if !self.same_shape(other) {
+            return Err(self.mismatch(other));
+        }
+        for (a, b) in self.inner.iter_mut().zip(&other.inner) {
+            f(a, b);
+        }
+        Ok(())
+    }
+}
+
+impl_op!(Add, add, checked_add);
+impl_op!(Sub, sub, checked_sub);
+impl_op!(Mul, mul, checked_mul);
+
+#[cfg(test)]
+mod test {
+    use crate::{MultiVec, ShapeError};
+
+    #[test]
+    fn test_elementwise() {
+        let a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32);
+        let b = MultiVec::<1, _>::from_fn(2, [3], |_, _| 2);
+        assert_eq!(
+            &a + &b,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32 + 2)
+        );
+        assert_eq!(
+            &a - &b,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32 - 2)
+        );
+        assert_eq!(
+            &a * &b,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32 * 2)
+        );
+    }
+
+    #[test]
+    fn test_scalar() {
+        let a = MultiVec::<2, _>::from_fn(2, [2, 2], |i, [j, k]| i + j + k);
+        assert_eq!(
+            a.clone() * 3,
+            MultiVec::<2, _>::from_fn(2, [2, 2], |i, [j, k]| (i + j + k) * 3)
+        );
+        assert_eq!(
+            a.clone() + 1,
+            MultiVec::<2, _>::from_fn(2, [2, 2], |i, [j, k]| i + j + k + 1)
+        );
+        assert_eq!(
+            a - 0,
+            MultiVec::<2, _>::from_fn(2, [2, 2], |i, [j, k]| i + j + k)
+        );
+    }
+
+    #[test]
+    fn test_checked_mismatch() {
+        let a = MultiVec::<1, u8>::filled_default(2, [3]);
+        let b = MultiVec::<1, u8>::filled_default(3, [2]);
+        assert_eq!(
+            a.checked_add(&b),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 3],
+                right: vec![3, 2]
+            })
+        );
+        let c = MultiVec::<1, u8>::filled_default(3, [3]);
+        assert!(a.checked_mul(&c).is_err());
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let a = MultiVec::<1, _>::from_fn(2, [2], |i, [j]| (i * 2 + j) as f64);
+        let b = MultiVec::<1, _>::from_fn(2, [2], |i, [j]| (i * 2 + j) as f64 + 1e-9);
+        assert!(a.approx_eq(&b, 1e-6));
+        assert!(b.approx_eq(&a, 1e-6));
+        assert!(!a.approx_eq(&b, 1e-12));
+        assert!(!a.approx_eq(&MultiVec::<1, _>::from_fn(4, [1], |i, _| i as f64), 1.0));
+
+        let nan = MultiVec::<1, _>::from_fn(2, [2], |_, _| f64::NAN);
+        assert!(!nan.approx_eq(&nan, 1.0));
+    }
+
+    #[test]
+    fn test_broadcast_with() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i * 12 + j * 4 + k) as i32);
+        let bias = MultiVec::<1, _>::from_fn(3, [4], |j, [k]| (j * 10 + k) as i32);
+        let sum = m.broadcast_with(&bias, |a, b| a + b).unwrap();
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    assert_eq!(sum[i][j][k], m[i][j][k] + bias[j][k]);
+                }
+            }
+        }
+
+        let row = MultiVec::<0, _>::from_fn(3, [], |k, _| k);
+        assert_eq!(
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| i * j).broadcast_with(&row, |a, b| (*a, *b)),
+            Ok(MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * j, j)))
+        );
+
+        let wrong = MultiVec::<1, i32>::filled_default(4, [3]);
+        assert_eq!(
+            m.broadcast_with(&wrong, |a, b| a + b),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 3, 4],
+                right: vec![4, 3]
+            })
+        );
+    }
+
+    #[test]
+    fn test_zip_mut_with() {
+        let mut a = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 3 + j) as i32);
+        let b = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i + j) as u8);
+        a.zip_mut_with(&b, |a, b| *a += *b as i32).unwrap();
+        assert_eq!(
+            a,
+            MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i * 4 + j * 2) as i32)
+        );
+
+        let c = MultiVec::<1, u8>::filled_default(3, [2]);
+        assert_eq!(
+            a.zip_mut_with(&c, |_, _| unreachable!()),
+            Err(ShapeError::Mismatch {
+                left: vec![2, 3],
+                right: vec![3, 2]
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "incompatible")]
+    fn test_add_mismatch() {
+        let a = MultiVec::<1, u8>::filled_default(2, [3]);
+        let b = MultiVec::<1, u8>::filled_default(3, [2]);
+        _ = &a + &b;
+    }
+}
@@ -0,0 +1,122 @@
+use ::rayon::{iter::Either, prelude::*};
+
+use crate::{DataMut, MultiVecBase, MultiVecMut, NonEmptyArray, NonEmptyArrayExt};
+
+impl<D: DataMut<T>, const N: usize, T: Send> MultiVecBase<D, N, T> {
+    /// Parallel counterpart of `rows_mut`. Each row is a disjoint chunk of the buffer.
+    pub fn par_rows_mut<const N_MINUS_ONE: usize>(
+        &mut self,
+    ) -> impl IndexedParallelIterator<Item = MultiVecMut<'_, N_MINUS_ONE, T>> + '_
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let (stride, outer) = (self.stride(), self.outer);
+        let (size, sizes) = self.sizes.arr_split_first();
+        let (size, sizes) = (*size, *sizes);
+        let row = move |slice| MultiVecBase::from_parts(slice, size, sizes);
+        if stride == 0 {
+            // Rows without elements can't be carved out of the buffer, so hand out empty slices.
+            Either::Left(
+                (0..outer)
+                    .into_par_iter()
+                    .map(move |_| row(Default::default())),
+            )
+        } else {
+            Either::Right(self.slice_mut().par_chunks_exact_mut(stride).map(row))
+        }
+    }
+
+    /// Calls `f` in parallel on consecutive chunks of `chunk_rows` outer rows. The last chunk is
+    /// shorter if the rows don't divide evenly.
+    pub fn par_for_each_chunk(
+        &mut self,
+        chunk_rows: usize,
+        f: impl Fn(MultiVecMut<'_, N, T>) + Sync,
+    ) {
+        assert!(chunk_rows != 0, "chunk size must be non-zero");
+        let (stride, outer, sizes) = (self.stride(), self.outer, self.sizes);
+        if stride == 0 {
+            (0..outer.div_ceil(chunk_rows))
+                .into_par_iter()
+                .for_each(|c| {
+                    let rows = chunk_rows.min(outer - c * chunk_rows);
+                    f(MultiVecBase::from_parts(Default::default(), rows, sizes));
+                });
+        } else {
+            self.slice_mut()
+                .par_chunks_mut(chunk_rows.saturating_mul(stride))
+                .for_each(|slice| {
+                    let rows = slice.len() / stride;
+                    f(MultiVecBase::from_parts(slice, rows, sizes));
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use ::rayon::prelude::*;
+
+    use crate::MultiVec;
+
+    #[test]
+    fn test_par_rows_mut() {
+        let mut parallel = MultiVec::<2, _>::from_fn(8, [3, 4], |i, [j, k]| i + j * k);
+        let mut serial = parallel.clone();
+
+        parallel
+            .par_rows_mut()
+            .enumerate()
+            .for_each(|(i, mut row)| {
+                row.indexed_iter_mut()
+                    .for_each(|([j, k], v): ([usize; 2], _)| *v = *v * 2 + i + j + k);
+            });
+        for (i, mut row) in serial.rows_mut().enumerate() {
+            row.indexed_iter_mut()
+                .for_each(|([j, k], v): ([usize; 2], _)| *v = *v * 2 + i + j + k);
+        }
+        assert_eq!(parallel, serial);
+        assert_eq!(MultiVec::<1, u8>::default().par_rows_mut().count(), 0);
+    }
+
+    #[test]
+    fn test_par_for_each_chunk() {
+        let mut m = MultiVec::<1, _>::from_fn(7, [3], |i, [j]| (i, j, 0));
+        m.par_for_each_chunk(3, |mut chunk| {
+            let rows = chunk.size();
+            chunk.iter_mut().for_each(|v| v.2 = rows);
+        });
+        assert_eq!(
+            m,
+            MultiVec::<1, _>::from_fn(7, [3], |i, [j]| (i, j, if i < 6 { 3 } else { 1 }))
+        );
+
+        // A held guard is `Sync` but not `Send`, and the closure is only shared between threads.
+        let lock = Mutex::new(7);
+        let guard = lock.lock().unwrap();
+        let mut m = MultiVec::<1, u8>::filled_default(4, [2]);
+        m.par_for_each_chunk(3, move |mut chunk| {
+            chunk.iter_mut().for_each(|v| *v = *guard)
+        });
+        assert!(m.iter().all(|v| *v == 7));
+
+        let mut m = MultiVec::<1, u8>::filled_default(5, [0]);
+        let rows = AtomicUsize::new(0);
+        m.par_for_each_chunk(2, |chunk| {
+            assert_eq!(chunk.shape()[1], 0);
+            rows.fetch_add(chunk.size(), Ordering::Relaxed);
+        });
+        assert_eq!(rows.into_inner(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_par_for_each_chunk_zero() {
+        MultiVec::<1, u8>::filled_default(2, [2]).par_for_each_chunk(0, |_| {});
+    }
+}
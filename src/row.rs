@@ -0,0 +1,124 @@
+use core::{
+    fmt::Debug,
+    ops::{Index, IndexMut},
+    ptr,
+};
+
+use crate::{checked_flat_of, MultiVecBase, MultiVecMut, MultiVecRef, NonEmptyArray, Refs};
+
+pub(crate) struct RowData<const N: usize, T> {
+    slice: *mut [T],
+    outer: usize,
+    sizes: [usize; N],
+    refs: Refs,
+}
+
+impl<const N: usize, T> RowData<N, T> {
+    pub(crate) fn shared(view: MultiVecRef<'_, N, T>) -> Self {
+        Self::new(view.inner as *const [T] as *mut [T], view.outer, view.sizes)
+    }
+
+    pub(crate) fn unique(view: MultiVecMut<'_, N, T>) -> Self {
+        Self::new(view.inner as *mut [T], view.outer, view.sizes)
+    }
+
+    fn new(slice: *mut [T], outer: usize, sizes: [usize; N]) -> Self {
+        Self {
+            slice,
+            outer,
+            sizes,
+            refs: Refs::new(),
+        }
+    }
+}
+
+/// An outer row of a grid, as returned by indexing it with a single `usize`.
+///
+/// This is what makes `m[i][j][k]` work like it does for nested `Vec`s. Each row is allocated
+/// when it is indexed and lives until the grid it came from is dropped or indexed mutably, so
+/// prefer [`row`](MultiVecBase::row) or `m[[i, j, k]]` in hot loops.
+///
+/// A row is unsized, so it can only be reached through a reference and never moved out of the
+/// grid.
+#[repr(transparent)]
+pub struct Row<const N: usize, T>([RowData<N, T>]);
+
+unsafe impl<const N: usize, T: Send> Send for Row<N, T> {}
+unsafe impl<const N: usize, T: Sync> Sync for Row<N, T> {}
+
+impl<const N: usize, T> Row<N, T> {
+    /// Moves `data` into `refs` and returns a pointer to it as a row, which is valid for as long
+    /// as both `refs` and the elements `data` points to are.
+    pub(crate) fn alloc(refs: &Refs, data: RowData<N, T>) -> *mut Self {
+        let ptr = refs.add([data]);
+        ptr::slice_from_raw_parts_mut(ptr.cast::<RowData<N, T>>(), 1) as *mut Self
+    }
+
+    fn data(&self) -> &RowData<N, T> {
+        &self.0[0]
+    }
+
+    pub(crate) fn refs(&self) -> &Refs {
+        &self.data().refs
+    }
+
+    pub(crate) fn refs_mut(&mut self) -> &mut Refs {
+        &mut self.0[0].refs
+    }
+
+    /// Borrows the row as a shared view.
+    pub fn view(&self) -> MultiVecRef<'_, N, T> {
+        let data = self.data();
+        MultiVecBase::from_parts(unsafe { &*data.slice }, data.outer, data.sizes)
+    }
+
+    /// Borrows the row as a unique view.
+    pub fn view_mut(&mut self) -> MultiVecMut<'_, N, T> {
+        let data = &mut self.0[0];
+        MultiVecBase::from_parts(unsafe { &mut *data.slice }, data.outer, data.sizes)
+    }
+}
+
+impl<T> Index<usize> for Row<0, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        unsafe { &(*self.data().slice)[index] }
+    }
+}
+
+impl<T> IndexMut<usize> for Row<0, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        unsafe { &mut (*self.0[0].slice)[index] }
+    }
+}
+
+impl<const N: usize, const N_PLUS_ONE: usize, T> Index<[usize; N_PLUS_ONE]> for Row<N, T>
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    type Output = T;
+
+    fn index(&self, coords: [usize; N_PLUS_ONE]) -> &Self::Output {
+        let view = self.view();
+        let index = checked_flat_of(view.offsets(), view.shape(), &coords);
+        unsafe { &(*self.data().slice)[index] }
+    }
+}
+
+impl<const N: usize, const N_PLUS_ONE: usize, T> IndexMut<[usize; N_PLUS_ONE]> for Row<N, T>
+where
+    [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+{
+    fn index_mut(&mut self, coords: [usize; N_PLUS_ONE]) -> &mut Self::Output {
+        let view = self.view();
+        let index = checked_flat_of(view.offsets(), view.shape(), &coords);
+        unsafe { &mut (*self.0[0].slice)[index] }
+    }
+}
+
+impl<const N: usize, T: Debug> Debug for Row<N, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.view().fmt_named(f, "Row")
+    }
+}
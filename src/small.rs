@@ -0,0 +1,135 @@
+use core::fmt::Debug;
+
+use smallvec::SmallVec;
+
+use crate::{
+    data::Sealed, len_of, offsets_of, split_coords_of, Data, DataMut, MultiVec, MultiVecBase,
+};
+
+/// A `MultiVec` that stores up to `CAP` elements inline and only allocates when it grows past
+/// that.
+pub type SmallMultiVec<const CAP: usize, const N: usize, T> =
+    MultiVecBase<SmallVec<[T; CAP]>, N, T>;
+
+impl<const CAP: usize, T> Sealed for SmallVec<[T; CAP]> {}
+
+impl<const CAP: usize, T> Data<T> for SmallVec<[T; CAP]> {
+    fn slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<const CAP: usize, T> DataMut<T> for SmallVec<[T; CAP]> {
+    fn slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<const CAP: usize, const N: usize, T: PartialEq> PartialEq for SmallMultiVec<CAP, N, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.outer == other.outer && self.sizes == other.sizes && self.inner == other.inner
+    }
+}
+
+impl<const CAP: usize, const N: usize, T: Eq> Eq for SmallMultiVec<CAP, N, T> {}
+
+impl<const CAP: usize, const N: usize, T: Debug> Debug for SmallMultiVec<CAP, N, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_named(f, "SmallMultiVec")
+    }
+}
+
+impl<const CAP: usize, const N: usize, T> From<MultiVec<N, T>> for SmallMultiVec<CAP, N, T> {
+    fn from(m: MultiVec<N, T>) -> Self {
+        let inner = match m.inner.len() {
+            len if len <= CAP => m.inner.into_iter().collect(),
+            _ => SmallVec::from_vec(m.inner),
+        };
+        Self::from_parts(inner, m.outer, m.sizes)
+    }
+}
+
+impl<const CAP: usize, const N: usize, T> From<SmallMultiVec<CAP, N, T>> for MultiVec<N, T> {
+    fn from(m: SmallMultiVec<CAP, N, T>) -> Self {
+        MultiVec::from_parts(m.inner.into_vec(), m.outer, m.sizes)
+    }
+}
+
+impl<const CAP: usize, const N: usize, T> SmallMultiVec<CAP, N, T> {
+    pub fn from_fn(
+        outer_size: usize,
+        sizes: [usize; N],
+        mut f: impl FnMut(usize, [usize; N]) -> T,
+    ) -> Self {
+        let offsets = offsets_of(sizes).unwrap_or_else(|e| panic!("{e}"));
+        let len = len_of(outer_size, &offsets).unwrap_or_else(|e| panic!("{e}"));
+        let inner = (0..len)
+            .map(|index| {
+                let (i, coords) = split_coords_of(&offsets, index);
+                f(i, coords)
+            })
+            .collect();
+        Self::from_parts(inner, outer_size, sizes)
+    }
+
+    pub fn spilled(&self) -> bool {
+        self.inner.spilled()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{MultiVec, SmallMultiVec};
+
+    #[test]
+    fn test_spill() {
+        let small = SmallMultiVec::<6, 1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        assert!(!small.spilled());
+        let large = SmallMultiVec::<6, 1, _>::from_fn(3, [3], |i, [j]| (i, j));
+        assert!(large.spilled());
+        assert_eq!(
+            MultiVec::from(large),
+            MultiVec::<1, _>::from_fn(3, [3], |i, [j]| (i, j))
+        );
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut m = SmallMultiVec::<8, 1, u8>::from_fn(2, [3], |_, _| 0);
+        m[[1, 2]] = 5;
+        m.row_mut(0)[1] = 3;
+        m.iter_mut().for_each(|v| *v += 1);
+        assert_eq!(m.as_flat_slice(), [1, 4, 1, 1, 1, 6]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_multi_vec(
+            outer_size in 0..5usize,
+            sizes in prop::array::uniform2(0..5usize),
+        ) {
+            let f = |i: usize, [j, k]: [usize; 2]| (i, j, k);
+            let small = SmallMultiVec::<16, 2, _>::from_fn(outer_size, sizes, f);
+            let m = MultiVec::<2, _>::from_fn(outer_size, sizes, f);
+            prop_assert_eq!(small.size(), m.size());
+            prop_assert_eq!(small.shape(), m.shape());
+            prop_assert!(small.iter().eq(m.iter()));
+            prop_assert_eq!(
+                format!("{small:?}"),
+                format!("{m:?}").replace("MultiVec", "SmallMultiVec")
+            );
+            for i in 0..outer_size {
+                for j in 0..sizes[0] {
+                    for k in 0..sizes[1] {
+                        prop_assert_eq!(small[[i, j, k]], m[[i, j, k]]);
+                        prop_assert_eq!(small.row(i).row(j)[k], m.row(i).row(j)[k]);
+                    }
+                }
+            }
+            prop_assert_eq!(SmallMultiVec::<16, 2, _>::from(m.clone()), small.clone());
+            prop_assert_eq!(MultiVec::from(small), m);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use alloc::vec::Vec;
+
+use crate::{coords_of, flat_of, offsets_of, MultiVec, NonEmptyArray, NonEmptyArrayExt};
+
+impl<const N: usize, T> MultiVec<N, T> {
+    pub fn permute_axes<const N_PLUS_ONE: usize>(&self, order: [usize; N_PLUS_ONE]) -> Self
+    where
+        T: Clone,
+        [usize; N_PLUS_ONE]: NonEmptyArray<N>,
+    {
+        let mut seen = [false; N_PLUS_ONE];
+        for &axis in &order {
+            assert!(
+                axis < N_PLUS_ONE && !seen[axis],
+                "{order:?} is not a permutation of the axes"
+            );
+            seen[axis] = true;
+        }
+
+        let shape: [usize; N_PLUS_ONE] = self.shape();
+        let new_shape = order.map(|axis| shape[axis]);
+        let sizes = new_shape[1..].try_into().unwrap();
+        let offsets = offsets_of(sizes).unwrap();
+        let inner = (0..self.inner.len())
+            .map(|i| {
+                let coords: [usize; N_PLUS_ONE] = coords_of(&offsets, i);
+                let mut source = [0; N_PLUS_ONE];
+                for (axis, coord) in order.iter().zip(coords) {
+                    source[*axis] = coord;
+                }
+                self.inner[flat_of(&self.offsets, &source)].clone()
+            })
+            .collect();
+
+        Self::from_parts(inner, new_shape[0], sizes)
+    }
+
+    pub fn flatten_outer<const N_MINUS_ONE: usize>(self) -> MultiVec<N_MINUS_ONE, T>
+    where
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let (size, sizes) = self.sizes.arr_split_first();
+        MultiVec::from_parts(self.inner, self.outer * size, *sizes)
+    }
+
+    pub fn rotate_left(&mut self, mid: usize) {
+        let stride = self.stride();
+        if let Some(mid) = mid.checked_rem(self.size()) {
+            self.inner.rotate_left(mid * stride);
+        }
+    }
+
+    pub fn rotate_right(&mut self, mid: usize) {
+        let stride = self.stride();
+        if let Some(mid) = mid.checked_rem(self.size()) {
+            self.inner.rotate_right(mid * stride);
+        }
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        let size = self.size();
+        assert!(
+            a < size && b < size,
+            "rows {a} and {b} must both be less than the size {size}"
+        );
+        let stride = self.stride();
+        let (a, b) = (a.min(b), a.max(b));
+        if a != b {
+            let (top, bottom) = self.inner.split_at_mut(b * stride);
+            top[a * stride..(a + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+        }
+    }
+
+    pub fn swap_outer_two<const N_MINUS_ONE: usize>(&self) -> Self
+    where
+        T: Clone,
+        [usize; N]: NonEmptyArray<N_MINUS_ONE, Item = usize>,
+    {
+        let stride = self.stride();
+        let block = self.offsets.get(1).cloned().unwrap_or(1);
+        let (outer_size, inner_size) = (self.size(), self.sizes[0]);
+        let mut inner = Vec::with_capacity(self.inner.len());
+        for j in 0..inner_size {
+            for i in 0..outer_size {
+                let start = i * stride + j * block;
+                inner.extend_from_slice(&self.inner[start..start + block]);
+            }
+        }
+
+        let mut sizes = self.sizes;
+        sizes[0] = outer_size;
+        Self::from_parts(inner, inner_size, sizes)
+    }
+
+    pub fn flip(&mut self, axis: usize) {
+        assert!(axis <= N, "axis {axis} is out of range for rank {}", N + 1);
+        if self.inner.is_empty() {
+            return;
+        }
+        let block = axis
+            .checked_sub(1)
+            .map_or(self.inner.len(), |axis| self.offsets[axis]);
+        let stride = self.offsets.get(axis).cloned().unwrap_or(1);
+        for block in self.inner.chunks_exact_mut(block) {
+            block.reverse();
+            for chunk in block.chunks_exact_mut(stride) {
+                chunk.reverse();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::MultiVec;
+
+    #[test]
+    fn test_permute_axes() {
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        let t = m.permute_axes([1, 0]);
+        assert_eq!(t.shape(), [3, 2]);
+        assert_eq!(t, MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (j, i)));
+
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let p = m.permute_axes([2, 0, 1]);
+        assert_eq!(p.shape(), [4, 2, 3]);
+        assert_eq!(p[3][1][2], (1, 2, 3));
+
+        let m = MultiVec::<2, u8>::filled_default(3, [0, 5]);
+        assert_eq!(m.permute_axes([2, 0, 1]).shape(), [5, 3, 0]);
+        assert_eq!(m.swap_outer_two().shape(), [0, 3, 5]);
+        assert_eq!(m.flatten_outer().shape(), [0, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_permute_axes_invalid() {
+        MultiVec::<2, u8>::filled_default(2, [3, 4]).permute_axes([0, 1, 1]);
+    }
+
+    #[test]
+    fn test_flatten_outer() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let flat = m.flatten_outer();
+        assert_eq!(flat.shape(), [6, 4]);
+        assert_eq!(
+            flat,
+            MultiVec::<1, _>::from_fn(6, [4], |i, [k]| (i / 3, i % 3, k))
+        );
+        assert_eq!(flat.flatten_outer().shape(), [24]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let m = MultiVec::<1, _>::from_fn(4, [2], |i, [j]| (i, j));
+        let mut rotated = m.clone();
+        rotated.rotate_left(1);
+        assert_eq!(
+            rotated,
+            MultiVec::<1, _>::from_fn(4, [2], |i, [j]| ((i + 1) % 4, j))
+        );
+        rotated.rotate_right(6);
+        assert_eq!(
+            rotated,
+            MultiVec::<1, _>::from_fn(4, [2], |i, [j]| ((i + 3) % 4, j))
+        );
+
+        for mid in [0, 4, 8] {
+            let mut rotated = m.clone();
+            rotated.rotate_left(mid);
+            assert_eq!(rotated, m);
+            rotated.rotate_right(mid);
+            assert_eq!(rotated, m);
+        }
+
+        let mut empty = MultiVec::<1, u8>::filled_default(0, [2]);
+        empty.rotate_left(3);
+        assert_eq!(empty.shape(), [0, 2]);
+    }
+
+    #[test]
+    fn test_swap_rows() {
+        let mut m = MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| (i, j, k));
+        m.swap_rows(2, 0);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| ([2, 1, 0][i], j, k))
+        );
+        m.swap_rows(1, 1);
+        m.swap_rows(0, 2);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(3, [2, 2], |i, [j, k]| (i, j, k))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "less than the size 3")]
+    fn test_swap_rows_out_of_range() {
+        MultiVec::<1, u8>::filled_default(3, [2]).swap_rows(0, 3);
+    }
+
+    #[test]
+    fn test_swap_outer_two() {
+        let m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        let swapped = m.swap_outer_two();
+        assert_eq!(swapped.shape(), [3, 2, 4]);
+        assert_eq!(swapped, m.permute_axes([1, 0, 2]));
+
+        let m = MultiVec::<1, _>::from_fn(2, [3], |i, [j]| (i, j));
+        assert_eq!(
+            m.swap_outer_two(),
+            MultiVec::<1, _>::from_fn(3, [2], |i, [j]| (j, i))
+        );
+    }
+
+    #[test]
+    fn test_flip() {
+        let mut m = MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (i, j, k));
+        m.flip(0);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (1 - i, j, k))
+        );
+        m.flip(1);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (1 - i, 2 - j, k))
+        );
+        m.flip(2);
+        assert_eq!(
+            m,
+            MultiVec::<2, _>::from_fn(2, [3, 4], |i, [j, k]| (1 - i, 2 - j, 3 - k))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_flip_invalid_axis() {
+        MultiVec::<1, u8>::filled_default(2, [3]).flip(2);
+    }
+
+    proptest! {
+        #[test]
+        fn test_permute_axes_inverse(
+            outer_size in 1..6usize,
+            sizes in prop::array::uniform2(1..6usize),
+            order in Just([0, 1, 2]).prop_shuffle(),
+        ) {
+            let m = MultiVec::<2, _>::from_fn(outer_size, sizes, |i, [j, k]| (i, j, k));
+            let mut inverse = [0; 3];
+            for (i, axis) in order.iter().enumerate() {
+                inverse[*axis] = i;
+            }
+            prop_assert_eq!(m.permute_axes(order).permute_axes(inverse), m);
+        }
+
+        #[test]
+        fn test_flip_twice(
+            outer_size in 0..6usize,
+            sizes in prop::array::uniform2(0..6usize),
+            axis in 0..3usize,
+        ) {
+            let m = MultiVec::<2, _>::from_fn(outer_size, sizes, |i, [j, k]| (i, j, k));
+            let mut flipped = m.clone();
+            flipped.flip(axis);
+            flipped.flip(axis);
+            prop_assert_eq!(flipped, m);
+        }
+
+        #[test]
+        fn test_swap_outer_two_twice(
+            outer_size in 1..6usize,
+            sizes in prop::array::uniform2(0..6usize),
+        ) {
+            let m = MultiVec::<2, _>::from_fn(outer_size, sizes, |i, [j, k]| (i, j, k));
+            prop_assert_eq!(m.swap_outer_two().swap_outer_two(), m);
+        }
+    }
+}